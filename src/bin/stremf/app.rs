@@ -1,14 +1,40 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::BufWriter;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
+use serde_json::Value;
 use strem::datastream::io::exporter::DataExporter;
 use stremf::config::Configuration;
+use stremf::schema::argoverse::Argoverse;
+use stremf::schema::bdd100k::Bdd100k;
+use stremf::schema::cityscapes::Cityscapes;
+use stremf::schema::coco::Coco;
+use stremf::schema::csv::Csv;
+use stremf::schema::cvat::Cvat;
+use stremf::schema::dair::DairV2x;
+use stremf::schema::kitti::kitti360::Kitti360;
+use stremf::schema::kitti::tracking::KittiTracking;
+use stremf::schema::kitti::Kitti;
+use stremf::schema::labelme::LabelMe;
+use stremf::schema::labelstudio::LabelStudio;
+use stremf::schema::mcap::Mcap;
+use stremf::schema::mot::Mot;
+use stremf::schema::nuimages::NuImages;
+use stremf::schema::nuplan::NuPlan;
 use stremf::schema::nuscenes::NuScenes;
+use stremf::schema::openimages::OpenImages;
+use stremf::schema::openlabel::OpenLabel;
+use stremf::schema::pandaset::PandaSet;
+use stremf::schema::rosbag::Rosbag;
+use stremf::schema::strem::Strem;
+use stremf::schema::voc::Voc;
+use stremf::schema::waymo::Waymo;
+use stremf::schema::yolo::Yolo;
 use stremf::schema::{Schema, SchemaKind};
 
 pub struct App {
@@ -29,11 +55,49 @@ impl App {
         let config = self.configure()?;
 
         if let Some(infile) = &config.infile {
-            let schema = NuScenes::new(infile, &config);
-            let datastreams = schema.import()?;
+            let schema: Box<dyn Schema + '_> = match config.schema {
+                SchemaKind::Argoverse => Box::new(Argoverse::new(infile, &config)),
+                SchemaKind::Bdd100k => Box::new(Bdd100k::new(infile, &config)),
+                SchemaKind::Cityscapes => Box::new(Cityscapes::new(infile, &config)),
+                SchemaKind::Coco => Box::new(Coco::new(infile, &config)),
+                SchemaKind::Csv => Box::new(Csv::new(infile, &config)),
+                SchemaKind::Cvat => Box::new(Cvat::new(infile, &config)),
+                SchemaKind::DairV2x => Box::new(DairV2x::new(infile, &config)),
+                SchemaKind::Kitti => Box::new(Kitti::new(infile, &config)),
+                SchemaKind::Kitti360 => Box::new(Kitti360::new(infile, &config)),
+                SchemaKind::KittiTracking => Box::new(KittiTracking::new(infile, &config)),
+                SchemaKind::LabelMe => Box::new(LabelMe::new(infile, &config)),
+                SchemaKind::LabelStudio => Box::new(LabelStudio::new(infile, &config)),
+                SchemaKind::Lyft => Box::new(NuScenes::lyft(infile, &config)),
+                SchemaKind::Mcap => Box::new(Mcap::new(infile, &config)),
+                SchemaKind::Mot => Box::new(Mot::new(infile, &config)),
+                SchemaKind::NuImages => Box::new(NuImages::new(infile, &config)),
+                SchemaKind::NuPlan => Box::new(NuPlan::new(infile, &config)),
+                SchemaKind::NuScenes => Box::new(NuScenes::new(infile, &config)),
+                SchemaKind::OpenImages => Box::new(OpenImages::new(infile, &config)),
+                SchemaKind::OpenLabel => Box::new(OpenLabel::new(infile, &config)),
+                SchemaKind::PandaSet => Box::new(PandaSet::new(infile, &config)),
+                SchemaKind::Rosbag => Box::new(Rosbag::new(infile, &config)),
+                SchemaKind::Strem => Box::new(Strem::new(infile, &config)),
+                SchemaKind::Voc => Box::new(Voc::new(infile, &config)),
+                SchemaKind::Waymo => Box::new(Waymo::new(infile, &config)),
+                SchemaKind::Yolo => Box::new(Yolo::new(infile, &config)),
+            };
+
+            let (datastreams, metadata) = schema.import_with_metadata()?;
+            let mut exported = HashSet::new();
 
             for (name, frames) in datastreams {
-                let path = PathBuf::from(&config.outfile).join(format!("{}.json", name));
+                let filename = Self::filename(&name)?;
+
+                if !exported.insert(filename.clone()) {
+                    return Err(Box::new(AppError::from(format!(
+                        "duplicate stream name `{}`",
+                        filename
+                    ))));
+                }
+
+                let path = PathBuf::from(&config.outfile).join(format!("{}.json", filename));
 
                 // Write the data to the file.
                 //
@@ -49,12 +113,48 @@ impl App {
                         AppDebug::from(format!("exported... {}", path.display()))
                     );
                 }
+
+                // Write the stream-level metadata, if any.
+                //
+                // This is written alongside the stream as STREM does not
+                // support metadata within the stream itself.
+                if let Some(metadata) = metadata.get(&name) {
+                    let path =
+                        PathBuf::from(&config.outfile).join(format!("{}.meta.json", filename));
+
+                    serde_json::to_writer(BufWriter::new(File::create(&path)?), metadata)?;
+
+                    if config.debug {
+                        println!(
+                            "{}",
+                            AppDebug::from(format!("exported... {}", path.display()))
+                        );
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// The file name (without extension) of a stream.
+    ///
+    /// The stream names are taken from the datasets (e.g., a video path of
+    /// COCO or a column of CSV); therefore, any path separator is replaced
+    /// such that each stream is written within the output directory.
+    fn filename(name: &str) -> Result<String, Box<dyn Error>> {
+        let filename = name.replace(['/', '\\'], "_");
+
+        if filename.is_empty() || filename == "." || filename == ".." {
+            return Err(Box::new(AppError::from(format!(
+                "invalid stream name `{}`",
+                name
+            ))));
+        }
+
+        Ok(filename)
+    }
+
     /// Create a new [`Configuration`] from the set of [`ArgMatches`].
     ///
     /// This function also maps possible values to typed enumerations within the
@@ -64,7 +164,32 @@ impl App {
             infile: self.matches.get_one::<PathBuf>("input").cloned(),
             outfile: self.matches.get_one::<PathBuf>("FILE").unwrap().clone(),
             schema: match &self.matches.get_one::<String>("schema").unwrap()[..] {
+                "argoverse" => SchemaKind::Argoverse,
+                "bdd100k" => SchemaKind::Bdd100k,
+                "cityscapes" => SchemaKind::Cityscapes,
+                "coco" => SchemaKind::Coco,
+                "csv" => SchemaKind::Csv,
+                "cvat" => SchemaKind::Cvat,
+                "dair-v2x" => SchemaKind::DairV2x,
+                "kitti" => SchemaKind::Kitti,
+                "kitti-360" => SchemaKind::Kitti360,
+                "kitti-tracking" => SchemaKind::KittiTracking,
+                "labelme" => SchemaKind::LabelMe,
+                "labelstudio" => SchemaKind::LabelStudio,
+                "lyft" => SchemaKind::Lyft,
+                "mcap" => SchemaKind::Mcap,
+                "mot" => SchemaKind::Mot,
+                "nuimages" => SchemaKind::NuImages,
+                "nuplan" => SchemaKind::NuPlan,
                 "nuscenes" => SchemaKind::NuScenes,
+                "openimages" => SchemaKind::OpenImages,
+                "openlabel" => SchemaKind::OpenLabel,
+                "pandaset" => SchemaKind::PandaSet,
+                "rosbag" => SchemaKind::Rosbag,
+                "strem" => SchemaKind::Strem,
+                "voc" => SchemaKind::Voc,
+                "waymo" => SchemaKind::Waymo,
+                "yolo" => SchemaKind::Yolo,
                 x => {
                     return Err(Box::new(AppError::from(format!(
                         "unsupported schema: `{}`",
@@ -72,9 +197,28 @@ impl App {
                     ))))
                 }
             },
+            options: self.options()?,
             debug: self.matches.get_flag("debug"),
         })
     }
+
+    /// Read the schema options from the JSON5 file, if provided.
+    ///
+    /// The options are kept as a generic [`Value`] such that each schema may
+    /// deserialize its own section, accordingly.
+    fn options(&self) -> Result<Value, Box<dyn Error>> {
+        match self.matches.get_one::<PathBuf>("config") {
+            Some(path) => {
+                let data = fs::read_to_string(path).or(Err(Box::new(AppError::from(format!(
+                    "unable to open `{}`",
+                    path.display()
+                )))))?;
+
+                Ok(json5::from_str(&data)?)
+            }
+            None => Ok(Value::Null),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -29,9 +29,31 @@ pub fn build() -> Command {
                 .long("schema")
                 .action(ArgAction::Set)
                 .value_parser([
+                    PossibleValue::new("argoverse"),
+                    PossibleValue::new("bdd100k"),
+                    PossibleValue::new("cityscapes"),
                     PossibleValue::new("coco"),
+                    PossibleValue::new("csv"),
+                    PossibleValue::new("cvat"),
+                    PossibleValue::new("dair-v2x"),
+                    PossibleValue::new("kitti"),
+                    PossibleValue::new("kitti-360"),
+                    PossibleValue::new("kitti-tracking"),
+                    PossibleValue::new("labelme"),
+                    PossibleValue::new("labelstudio"),
+                    PossibleValue::new("lyft"),
+                    PossibleValue::new("mcap"),
+                    PossibleValue::new("mot"),
+                    PossibleValue::new("nuimages"),
+                    PossibleValue::new("nuplan"),
                     PossibleValue::new("nuscenes"),
+                    PossibleValue::new("openimages"),
+                    PossibleValue::new("openlabel"),
+                    PossibleValue::new("pandaset"),
+                    PossibleValue::new("rosbag"),
                     PossibleValue::new("strem"),
+                    PossibleValue::new("voc"),
+                    PossibleValue::new("waymo"),
                     PossibleValue::new("yolo"),
                 ])
                 .hide_possible_values(true)
@@ -40,6 +62,15 @@ pub fn build() -> Command {
                 .value_name("name")
                 .help("The input dataset schema"),
         )
+        .arg(
+            Arg::new("config")
+                .short('c')
+                .long("config")
+                .action(ArgAction::Set)
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("The path to the schema options file (JSON5)"),
+        )
         .arg(
             Arg::new("debug")
                 .short('d')
@@ -1,5 +1,9 @@
+use std::error::Error;
 use std::path::PathBuf;
 
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
 use crate::schema::SchemaKind;
 
 pub struct Configuration {
@@ -12,6 +16,25 @@ pub struct Configuration {
     /// The data schema of the [`self::file`].
     pub schema: SchemaKind,
 
+    /// The schema-specific options (e.g., filters), keyed by schema name.
+    pub options: Value,
+
     /// Print debug statements (when appropriate).
     pub debug: bool,
 }
+
+impl Configuration {
+    /// Deserialize the options of a schema.
+    ///
+    /// If no options are provided for the schema, then the default options
+    /// are used, accordingly.
+    pub fn options<T>(&self, schema: &str) -> Result<T, Box<dyn Error>>
+    where
+        T: DeserializeOwned + Default,
+    {
+        match self.options.get(schema) {
+            Some(options) => Ok(serde_json::from_value(options.clone())?),
+            None => Ok(T::default()),
+        }
+    }
+}
@@ -1,11 +1,50 @@
+use std::collections::HashMap;
 use std::error::Error;
 
-use strem::datastream::frame::Frame;
+use ::strem::datastream::frame::Frame;
+use serde_json::Value;
 
+pub mod argoverse;
+pub mod bdd100k;
+pub mod cityscapes;
+pub mod coco;
+pub mod csv;
+mod cuboid;
+pub mod cvat;
+pub mod dair;
+pub mod kitti;
+pub mod labelme;
+pub mod labelstudio;
+pub mod mcap;
+pub mod mot;
+pub mod nuimages;
+pub mod nuplan;
 pub mod nuscenes;
+pub mod openimages;
+pub mod openlabel;
+pub mod pandaset;
+mod ros;
+pub mod rosbag;
+pub mod strem;
+mod timeline;
+pub mod voc;
+pub mod waymo;
+pub mod yolo;
+
+/// The stream-level metadata (e.g., weather), keyed by stream name.
+pub type Metadata = HashMap<String, Value>;
 
 pub trait Schema {
     fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>>;
+
+    /// Import the set of streams along with their stream-level metadata.
+    ///
+    /// By default, no metadata is provided.
+    fn import_with_metadata(
+        &self,
+    ) -> Result<(Vec<(String, Vec<Frame>)>, Metadata), Box<dyn Error>> {
+        Ok((self.import()?, Metadata::new()))
+    }
 }
 
 /// The set of schemas supported.
@@ -13,5 +52,30 @@ pub trait Schema {
 /// This support only includes importing and not necessarily exporting. This is
 /// by design as this tool is for converting into STREM and not vice-versa.
 pub enum SchemaKind {
+    Argoverse,
+    Bdd100k,
+    Cityscapes,
+    Coco,
+    Csv,
+    Cvat,
+    DairV2x,
+    Kitti,
+    Kitti360,
+    KittiTracking,
+    LabelMe,
+    LabelStudio,
+    Lyft,
+    Mcap,
+    Mot,
+    NuImages,
+    NuPlan,
     NuScenes,
+    OpenImages,
+    OpenLabel,
+    PandaSet,
+    Rosbag,
+    Strem,
+    Voc,
+    Waymo,
+    Yolo,
 }
@@ -0,0 +1,395 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nalgebra::SMatrix as StaticMatrix;
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::cuboid::Cuboid;
+use super::timeline::{self, nearest};
+use super::Schema;
+
+use self::feather::Table as AvTable;
+use self::pose::Pose as AvPose;
+
+mod feather;
+mod pose;
+
+type Timestamp = i64;
+type SensorName = String;
+
+/// The nominal period of the cameras (i.e., 20Hz in nanoseconds).
+const PERIOD: Timestamp = 50_000_000;
+
+/// The intrinsics of a camera.
+struct AvCamera {
+    view: StaticMatrix<f64, 3, 3>,
+    width: f64,
+    height: f64,
+}
+
+/// The Argoverse 2 sensor dataset.
+///
+/// The root may either be a single log (i.e., containing `annotations.feather`)
+/// or a directory of logs (e.g., `sensor/train`). Each log forms a stream where
+/// each annotated sweep forms a [`Frame`].
+pub struct Argoverse<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Argoverse<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    /// Load a Feather-based table from the log.
+    fn load(&self, log: &Path, filename: &str) -> Result<AvTable, Box<dyn Error>> {
+        let path = log.join(filename);
+
+        let table = AvTable::open(&path).map_err(|e| {
+            ArgoverseError::from(format!("unable to read `{}`: {}", path.display(), e))
+        })?;
+
+        self.debug(&format!("arrow: read table from `{}`", path.display()));
+
+        Ok(table)
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", ArgoverseDebug::from(msg));
+        }
+    }
+
+    fn channel(&self, sensor: &str) -> Option<String> {
+        match sensor {
+            "ring_front_center" => Some(String::from("cam::front")),
+            "ring_front_left" => Some(String::from("cam::front::left")),
+            "ring_front_right" => Some(String::from("cam::front::right")),
+            "ring_rear_left" => Some(String::from("cam::back::left")),
+            "ring_rear_right" => Some(String::from("cam::back::right")),
+            "ring_side_left" => Some(String::from("cam::side::left")),
+            "ring_side_right" => Some(String::from("cam::side::right")),
+            _ => None,
+        }
+    }
+
+    /// Collect the set of log directories.
+    fn logs(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if self.root.join("annotations.feather").is_file() {
+            return Ok(vec![self.root.clone()]);
+        }
+
+        let mut logs = Vec::new();
+
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+
+            if path.join("annotations.feather").is_file() {
+                logs.push(path);
+            }
+        }
+
+        logs.sort();
+
+        Ok(logs)
+    }
+
+    /// Load the intrinsics of each camera.
+    fn cameras(&self, log: &Path) -> Result<BTreeMap<SensorName, AvCamera>, Box<dyn Error>> {
+        let table = self.load(log, "calibration/intrinsics.feather")?;
+
+        let names = table.string("sensor_name")?;
+        let [fx, fy, cx, cy] = [
+            table.f64("fx_px")?,
+            table.f64("fy_px")?,
+            table.f64("cx_px")?,
+            table.f64("cy_px")?,
+        ];
+
+        let [width, height] = [table.f64("width_px")?, table.f64("height_px")?];
+
+        Ok(names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let view = StaticMatrix::<f64, 3, 3>::new(
+                    fx[i], 0.0, cx[i], 0.0, fy[i], cy[i], 0.0, 0.0, 1.0,
+                );
+
+                (
+                    name,
+                    AvCamera {
+                        view,
+                        width: width[i],
+                        height: height[i],
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Collect the set of images of a camera, keyed by timestamp.
+    ///
+    /// The images are named after their timestamp in nanoseconds.
+    fn images(
+        &self,
+        log: &Path,
+        sensor: &str,
+    ) -> Result<BTreeMap<Timestamp, PathBuf>, Box<dyn Error>> {
+        let dir = log.join("sensors").join("cameras").join(sensor);
+        let mut images = BTreeMap::new();
+
+        if !dir.is_dir() {
+            return Ok(images);
+        }
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+
+            if let Some(timestamp) = path
+                .file_stem()
+                .and_then(|x| x.to_str())
+                .and_then(|x| x.parse::<Timestamp>().ok())
+            {
+                images.insert(timestamp, path);
+            }
+        }
+
+        Ok(images)
+    }
+
+    /// Load the set of annotations, grouped by (sweep) timestamp.
+    ///
+    /// The annotations are with respect to the ego-vehicle at the time of the
+    /// sweep.
+    fn annotations(
+        &self,
+        log: &Path,
+    ) -> Result<BTreeMap<Timestamp, Vec<(String, Cuboid)>>, Box<dyn Error>> {
+        let table = self.load(log, "annotations.feather")?;
+
+        let timestamps = table.i64("timestamp_ns")?;
+        let categories = table.string("category")?;
+
+        let [length, width, height] = [
+            table.f64("length_m")?,
+            table.f64("width_m")?,
+            table.f64("height_m")?,
+        ];
+
+        let [qw, qx, qy, qz] = [
+            table.f64("qw")?,
+            table.f64("qx")?,
+            table.f64("qy")?,
+            table.f64("qz")?,
+        ];
+
+        let [tx, ty, tz] = [table.f64("tx_m")?, table.f64("ty_m")?, table.f64("tz_m")?];
+
+        let mut res: BTreeMap<Timestamp, Vec<(String, Cuboid)>> = BTreeMap::new();
+
+        for (i, category) in categories.into_iter().enumerate() {
+            let cuboid = Cuboid::new(
+                [tx[i], ty[i], tz[i]],
+                [width[i], length[i], height[i]],
+                [qw[i], qx[i], qy[i], qz[i]],
+            );
+
+            res.entry(timestamps[i])
+                .or_default()
+                .push((category, cuboid));
+        }
+
+        Ok(res)
+    }
+
+    /// Project the set of annotations onto a camera.
+    ///
+    /// This includes: (1) moving the annotations from the ego-vehicle at the
+    /// time of the sweep into the city, (2) moving the annotations into the
+    /// ego-vehicle at the time of the image, and (3) moving the annotations
+    /// into the camera, accordingly.
+    fn project(
+        &self,
+        annotations: &[(String, Cuboid)],
+        sweep: &AvPose,
+        ego: &AvPose,
+        extrinsic: &AvPose,
+        camera: &AvCamera,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for (label, cuboid) in annotations {
+            let a = cuboid
+                .clone()
+                .out_of_frame(sweep.translation, sweep.rotation)
+                .into_frame(ego.translation, ego.rotation)
+                .into_frame(extrinsic.translation, extrinsic.rotation);
+
+            if a.inside(camera.view, camera.width, camera.height) {
+                res.entry(label.clone()).or_default().push(Annotation::new(
+                    label.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(a.region(camera.view)),
+                ));
+            }
+        }
+
+        res
+    }
+}
+
+impl Schema for Argoverse<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let mut datastreams = Vec::new();
+
+        for log in self.logs()? {
+            let name = log.file_name().unwrap().to_string_lossy().into_owned();
+
+            // Set up internal database.
+            //
+            // The calibration and poses of the log must first be set up in
+            // order to project the annotations onto each camera.
+            self.debug(&format!("building internal database of `{}`", name));
+
+            let cameras = self.cameras(&log)?;
+
+            let table = self.load(&log, "calibration/egovehicle_SE3_sensor.feather")?;
+            let extrinsics = AvPose::read(&table, table.string("sensor_name")?)?;
+
+            let table = self.load(&log, "city_SE3_egovehicle.feather")?;
+            let poses = AvPose::read(&table, table.i64("timestamp_ns")?)?;
+
+            let mut sensors = Vec::new();
+
+            for (sensor, camera) in cameras.iter() {
+                if let (Some(channel), Some(extrinsic)) =
+                    (self.channel(sensor), extrinsics.get(sensor))
+                {
+                    let images = self.images(&log, sensor)?;
+                    let tolerance = timeline::tolerance(&images, PERIOD);
+
+                    sensors.push((channel, camera, extrinsic, images, tolerance));
+                }
+            }
+
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each annotated sweep and associate the
+            // nearest image of each camera with it, accordingly. An image more
+            // than half a period away (e.g., a dropped frame) is not associated.
+            let mut frames = Vec::new();
+
+            for (index, (timestamp, annotations)) in self.annotations(&log)?.iter().enumerate() {
+                let mut frame = Frame::new(index);
+
+                let sweep = nearest(&poses, *timestamp)
+                    .ok_or_else(|| ArgoverseError::from(format!("{}: missing ego poses", name)))?
+                    .1;
+
+                for (channel, camera, extrinsic, images, tolerance) in sensors.iter() {
+                    let (image, ego) = match timeline::within(images, *timestamp, *tolerance) {
+                        Some((t, path)) => (
+                            Some(Image::new(
+                                ImageSource::File(path.clone()),
+                                camera.width as u32,
+                                camera.height as u32,
+                            )),
+                            nearest(&poses, t).map(|x| x.1).unwrap_or(sweep),
+                        ),
+                        None => (None, sweep),
+                    };
+
+                    let mut record = DetectionRecord::new(channel.clone(), image);
+                    record.annotations = self.project(annotations, sweep, ego, extrinsic, camera);
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArgoverseDebug {
+    msg: String,
+}
+
+impl From<&str> for ArgoverseDebug {
+    fn from(msg: &str) -> Self {
+        ArgoverseDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for ArgoverseDebug {
+    fn from(msg: String) -> Self {
+        ArgoverseDebug { msg }
+    }
+}
+
+impl fmt::Display for ArgoverseDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: argoverse: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArgoverseError {
+    msg: String,
+}
+
+impl From<&str> for ArgoverseError {
+    fn from(msg: &str) -> Self {
+        ArgoverseError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for ArgoverseError {
+    fn from(msg: String) -> Self {
+        ArgoverseError { msg }
+    }
+}
+
+impl fmt::Display for ArgoverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "argoverse: {}", self.msg)
+    }
+}
+
+impl Error for ArgoverseError {}
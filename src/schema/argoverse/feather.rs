@@ -0,0 +1,92 @@
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, Int64Type};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_cast::cast;
+use arrow_ipc::reader::FileReader;
+use arrow_schema::DataType;
+
+/// A table read from a Feather (v2) file.
+///
+/// Feather (v2) is the Arrow IPC file format; therefore, the table is read as
+/// a set of [`RecordBatch`] where each column is read (and cast) on demand.
+pub struct Table {
+    batches: Vec<RecordBatch>,
+}
+
+impl Table {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let reader = FileReader::try_new(File::open(path)?, None)?;
+        let batches = reader.collect::<Result<Vec<RecordBatch>, _>>()?;
+
+        Ok(Self { batches })
+    }
+
+    /// The number of rows of the table.
+    fn len(&self) -> usize {
+        self.batches.iter().map(|x| x.num_rows()).sum()
+    }
+
+    /// Read a column (across each batch) cast to the provided [`DataType`].
+    fn column(&self, name: &str, kind: &DataType) -> Result<Vec<ArrayRef>, Box<dyn Error>> {
+        let mut res = Vec::new();
+
+        for batch in self.batches.iter() {
+            let array = batch
+                .column_by_name(name)
+                .ok_or_else(|| format!("missing column `{}`", name))?;
+
+            res.push(cast(array, kind)?);
+        }
+
+        Ok(res)
+    }
+
+    pub fn f64(&self, name: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+        let mut res = Vec::with_capacity(self.len());
+
+        for array in self.column(name, &DataType::Float64)? {
+            res.extend(
+                array
+                    .as_primitive::<Float64Type>()
+                    .iter()
+                    .map(|x| x.unwrap_or(f64::NAN)),
+            );
+        }
+
+        Ok(res)
+    }
+
+    pub fn i64(&self, name: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+        let mut res = Vec::with_capacity(self.len());
+
+        for array in self.column(name, &DataType::Int64)? {
+            res.extend(
+                array
+                    .as_primitive::<Int64Type>()
+                    .iter()
+                    .map(|x| x.unwrap_or_default()),
+            );
+        }
+
+        Ok(res)
+    }
+
+    pub fn string(&self, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut res = Vec::with_capacity(self.len());
+
+        for array in self.column(name, &DataType::Utf8)? {
+            res.extend(
+                array
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|x| x.unwrap_or_default().to_string()),
+            );
+        }
+
+        Ok(res)
+    }
+}
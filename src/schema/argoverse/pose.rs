@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use super::feather::Table;
+
+/// A rigid transformation (e.g., `city_SE3_egovehicle`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pose {
+    /// x, y, z
+    pub translation: [f64; 3],
+    /// w, x, y, z
+    pub rotation: [f64; 4],
+}
+
+impl Pose {
+    /// Read the set of [`Pose`] from a table, keyed by the provided column.
+    ///
+    /// The rotation and translation are stored in the `qw`, `qx`, `qy`, `qz`
+    /// and `tx_m`, `ty_m`, `tz_m` columns, respectively.
+    pub fn read<K>(table: &Table, keys: Vec<K>) -> Result<BTreeMap<K, Pose>, Box<dyn Error>>
+    where
+        K: Ord,
+    {
+        let [qw, qx, qy, qz] = [
+            table.f64("qw")?,
+            table.f64("qx")?,
+            table.f64("qy")?,
+            table.f64("qz")?,
+        ];
+
+        let [tx, ty, tz] = [table.f64("tx_m")?, table.f64("ty_m")?, table.f64("tz_m")?];
+
+        Ok(keys
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| {
+                (
+                    key,
+                    Pose {
+                        translation: [tx[i], ty[i], tz[i]],
+                        rotation: [qw[i], qx[i], qy[i], qz[i]],
+                    },
+                )
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::{Metadata, Schema};
+
+use self::frame::Frame as BddFrame;
+use self::label::Label as BddLabel;
+use self::options::Options as BddOptions;
+
+mod frame;
+mod label;
+mod options;
+
+/// The dimensions of the BDD100K images, if the image is absent.
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+
+/// The BDD100K detection and tracking labels (Scalabel format).
+///
+/// The input may either be a single JSON file (e.g., the per-image detection
+/// labels) or a directory of JSON files (e.g., the per-video tracking labels).
+pub struct Bdd100k<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Bdd100k<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", BddDebug::from(msg));
+        }
+    }
+
+    /// Load the set of [`BddFrame`] from a Scalabel formatted file.
+    fn load(&self, path: &Path) -> Result<Vec<BddFrame>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(BddError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+        let data = serde_json::from_reader(reader)?;
+
+        self.debug(&format!(
+            "serde: deserialized data from `{}`",
+            path.display()
+        ));
+
+        Ok(data)
+    }
+
+    /// The set of label files to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "json").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Summarize the frame-level attributes into stream-level metadata.
+    ///
+    /// Each attribute (e.g., `weather`) maps to the set of distinct values
+    /// found across the frames of the stream.
+    fn summarize(&self, frames: &[BddFrame]) -> Value {
+        let mut res: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+        for frame in frames {
+            for (name, value) in frame.attributes.iter() {
+                let value = match value {
+                    Value::String(x) => x.clone(),
+                    x => x.to_string(),
+                };
+
+                res.entry(name.clone()).or_default().insert(value);
+            }
+        }
+
+        serde_json::json!(res)
+    }
+
+    /// Reference the image of a frame.
+    ///
+    /// The dimensions are read from the image header as the subsets (e.g.,
+    /// crops) are not all of the same size; however, if the image is absent,
+    /// then the size of the 100K images is assumed.
+    fn image(&self, frame: &BddFrame) -> Result<Image, Box<dyn Error>> {
+        let path = PathBuf::from(&frame.name);
+
+        let (width, height) = if path.is_file() {
+            let size = imagesize::size(&path).map_err(|e| {
+                BddError::from(format!("unable to read `{}`: {}", path.display(), e))
+            })?;
+
+            (size.width as u32, size.height as u32)
+        } else {
+            (WIDTH, HEIGHT)
+        };
+
+        Ok(Image::new(ImageSource::File(path), width, height))
+    }
+
+    fn annotations(
+        &self,
+        labels: &[BddLabel],
+        options: &BddOptions,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for label in labels.iter().filter(|x| options.keep_label(x)) {
+            // Only the `box2d` labels are considered.
+            //
+            // The remaining labels (e.g., lane markings, drivable areas) are
+            // skipped, accordingly.
+            if let Some(bbox) = &label.box2d {
+                let width = bbox.x2 - bbox.x1;
+                let height = bbox.y2 - bbox.y1;
+
+                res.entry(label.category.clone())
+                    .or_default()
+                    .push(Annotation::new(
+                        label.category.clone(),
+                        label.score.unwrap_or(1.0),
+                        BoundingBox::AxisAligned(aa::Region::new(
+                            Point::new(bbox.x1 + (width / 2.0), bbox.y1 + (height / 2.0)),
+                            width,
+                            height,
+                        )),
+                    ));
+            }
+        }
+
+        res
+    }
+}
+
+impl Schema for Bdd100k<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        Ok(self.import_with_metadata()?.0)
+    }
+
+    fn import_with_metadata(
+        &self,
+    ) -> Result<(Vec<(String, Vec<Frame>)>, Metadata), Box<dyn Error>> {
+        let mut metadata = Metadata::new();
+
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: BddOptions = self.config.options("bdd100k")?;
+
+        // Group the frames into streams.
+        //
+        // The tracking labels are grouped by the `videoName` whereas the
+        // detection labels (without a video) are grouped by the file. The
+        // frame filters apply to each detection label, individually.
+        let mut groups: BTreeMap<String, Vec<BddFrame>> = BTreeMap::new();
+
+        for path in self.files()? {
+            let stem = path
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("bdd100k"));
+
+            for frame in self.load(&path)? {
+                if frame.video_name.is_none() && !options.keep_frame(&frame) {
+                    continue;
+                }
+
+                let name = frame.video_name.clone().unwrap_or_else(|| stem.clone());
+                groups.entry(name).or_default().push(frame);
+            }
+        }
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each group and order the frames by their
+        // index (or name) to form a linear stream.
+        let mut datastreams = Vec::new();
+
+        for (name, mut group) in groups {
+            // The frame filters apply to a video as a whole.
+            //
+            // Dropping individual frames would otherwise cut holes into the
+            // timeline of the video; therefore, a video is only kept if each
+            // of its frames passes the filters.
+            let video = group.iter().any(|x| x.video_name.is_some());
+
+            if video && !group.iter().all(|x| options.keep_frame(x)) {
+                self.debug(&format!("skipped stream `{}`", name));
+                continue;
+            }
+
+            group.sort_by(|a, b| {
+                a.frame_index
+                    .cmp(&b.frame_index)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+
+            let mut frames = Vec::new();
+
+            for (index, data) in group.iter().enumerate() {
+                let mut frame = Frame::new(index);
+                let mut record =
+                    DetectionRecord::new(String::from("cam::front"), Some(self.image(data)?));
+
+                if let Some(labels) = &data.labels {
+                    record.annotations = self.annotations(labels, &options);
+                }
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            metadata.insert(name.clone(), self.summarize(&group));
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok((datastreams, metadata))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BddDebug {
+    msg: String,
+}
+
+impl From<&str> for BddDebug {
+    fn from(msg: &str) -> Self {
+        BddDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for BddDebug {
+    fn from(msg: String) -> Self {
+        BddDebug { msg }
+    }
+}
+
+impl fmt::Display for BddDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: bdd100k: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BddError {
+    msg: String,
+}
+
+impl From<&str> for BddError {
+    fn from(msg: &str) -> Self {
+        BddError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for BddError {
+    fn from(msg: String) -> Self {
+        BddError { msg }
+    }
+}
+
+impl fmt::Display for BddError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bdd100k: {}", self.msg)
+    }
+}
+
+impl Error for BddError {}
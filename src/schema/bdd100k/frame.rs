@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::label::Label;
+
+/// A single frame of the Scalabel format used by BDD100K.
+///
+/// The detection labels only provide the `name` whereas the tracking labels
+/// additionally provide the `videoName` and `frameIndex`.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Frame {
+    pub name: String,
+    pub video_name: Option<String>,
+    pub frame_index: Option<usize>,
+    /// weather, timeofday, scene
+    #[serde(default)]
+    pub attributes: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub labels: Option<Vec<Label>>,
+}
@@ -0,0 +1,33 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Box2d {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Label {
+    pub category: String,
+    /// occluded, truncated, crowd, trafficLightColor
+    #[serde(default)]
+    pub attributes: BTreeMap<String, Value>,
+    pub box2d: Option<Box2d>,
+    /// Only present in result files.
+    pub score: Option<f64>,
+}
+
+impl Label {
+    /// Check if a boolean attribute (e.g., `occluded`) is set.
+    pub fn flag(&self, name: &str) -> bool {
+        self.attributes
+            .get(name)
+            .and_then(|x| x.as_bool())
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+use super::frame::Frame;
+use super::label::Label;
+
+/// The set of filters applied to BDD100K labels.
+///
+/// By default, every frame and object is kept.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// Keep objects marked as `occluded`.
+    pub occluded: bool,
+    /// Keep objects marked as `truncated`.
+    pub truncated: bool,
+    /// The set of `weather` values of the frames (or videos) to keep.
+    pub weather: Vec<String>,
+    /// The set of `timeofday` values of the frames (or videos) to keep.
+    pub timeofday: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            occluded: true,
+            truncated: true,
+            weather: Vec::new(),
+            timeofday: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// Check if the [`Frame`] passes the set of filters.
+    ///
+    /// If a filter is set, then frames without the attribute are dropped. The
+    /// frames of a video are checked together, as a whole.
+    pub fn keep_frame(&self, frame: &Frame) -> bool {
+        for (name, values) in [("weather", &self.weather), ("timeofday", &self.timeofday)] {
+            if values.is_empty() {
+                continue;
+            }
+
+            match frame.attributes.get(name).and_then(|x| x.as_str()) {
+                Some(value) if values.iter().any(|x| x == value) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Check if the [`Label`] passes the set of filters.
+    pub fn keep_label(&self, label: &Label) -> bool {
+        (self.occluded || !label.flag("occluded")) && (self.truncated || !label.flag("truncated"))
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::options::Options as CityscapesOptions;
+use self::polygons::{Object as CityscapesObject, Polygons as CityscapesPolygons};
+
+mod options;
+mod polygons;
+
+type City = String;
+
+/// The suffix of the polygon annotations.
+const SUFFIX: &str = "_gtFine_polygons.json";
+
+/// A single annotated image of a city.
+///
+/// Each image is named `<city>_<sequence>_<frame>` (e.g.,
+/// `aachen_000000_000019`), where the sequence and frame number locate the
+/// image within the `leftImg8bit_sequence` recordings.
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    split: String,
+    id: String,
+    sequence: u64,
+    frame: u64,
+    path: PathBuf,
+}
+
+/// The Cityscapes (fine) polygon annotations.
+///
+/// The root is the dataset directory containing `gtFine` and `leftImg8bit`.
+/// The images of each city (across the splits) form a stream, ordered by
+/// sequence and frame number.
+pub struct Cityscapes<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Cityscapes<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", CityscapesDebug::from(msg));
+        }
+    }
+
+    /// Find the set of annotated images, grouped by city.
+    ///
+    /// The annotations are found at `gtFine/<split>/<city>/<id>_gtFine_polygons.json`.
+    fn entries(
+        &self,
+        options: &CityscapesOptions,
+    ) -> Result<BTreeMap<City, Vec<Entry>>, Box<dyn Error>> {
+        let dir = self.root.join("gtFine");
+
+        if !dir.is_dir() {
+            return Err(Box::new(CityscapesError::from(format!(
+                "missing `gtFine` directory at `{}`",
+                self.root.display()
+            ))));
+        }
+
+        let mut res: BTreeMap<City, Vec<Entry>> = BTreeMap::new();
+
+        for split in fs::read_dir(&dir)? {
+            let split = split?.path();
+            let name = split.file_name().unwrap().to_string_lossy().into_owned();
+
+            if !split.is_dir() || !options.keep_split(&name) {
+                continue;
+            }
+
+            for city in fs::read_dir(&split)? {
+                let city = city?.path();
+
+                if !city.is_dir() {
+                    continue;
+                }
+
+                for file in fs::read_dir(&city)? {
+                    let path = file?.path();
+                    let filename = path.file_name().unwrap().to_string_lossy().into_owned();
+
+                    let id = match filename.strip_suffix(SUFFIX) {
+                        Some(id) => id.to_string(),
+                        None => continue,
+                    };
+
+                    let mut fields = id.rsplitn(3, '_');
+
+                    let (frame, sequence, town) = match (
+                        fields.next().and_then(|x| x.parse().ok()),
+                        fields.next().and_then(|x| x.parse().ok()),
+                        fields.next(),
+                    ) {
+                        (Some(frame), Some(sequence), Some(town)) => (frame, sequence, town),
+                        _ => {
+                            return Err(Box::new(CityscapesError::from(format!(
+                                "invalid annotation name `{}`",
+                                filename
+                            ))))
+                        }
+                    };
+
+                    res.entry(town.to_string()).or_default().push(Entry {
+                        split: name.clone(),
+                        id: id.clone(),
+                        sequence,
+                        frame,
+                        path,
+                    });
+                }
+            }
+
+            self.debug(&format!("found split `{}`", name));
+        }
+
+        for entries in res.values_mut() {
+            entries.sort_by_key(|x| (x.sequence, x.frame));
+        }
+
+        Ok(res)
+    }
+
+    /// Load the [`CityscapesPolygons`] of a single image.
+    fn load(&self, path: &Path) -> Result<CityscapesPolygons, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(CityscapesError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+
+        Ok(serde_json::from_reader(reader)
+            .map_err(|e| CityscapesError::from(format!("{}: {}", path.display(), e)))?)
+    }
+
+    /// Reference the `leftImg8bit` image of an entry.
+    fn image(&self, city: &str, entry: &Entry, polygons: &CityscapesPolygons) -> Image {
+        let path = self
+            .root
+            .join("leftImg8bit")
+            .join(&entry.split)
+            .join(city)
+            .join(format!("{}_leftImg8bit.png", entry.id));
+
+        Image::new(
+            ImageSource::File(path),
+            polygons.img_width,
+            polygons.img_height,
+        )
+    }
+
+    fn annotations(
+        &self,
+        objects: &[CityscapesObject],
+        options: &CityscapesOptions,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for object in objects.iter() {
+            let (label, [xmin, ymin, xmax, ymax]) =
+                match (options.label(&object.label), object.bbox()) {
+                    (Some(label), Some(bbox)) => (label, bbox),
+                    _ => continue,
+                };
+
+            let width = xmax - xmin;
+            let height = ymax - ymin;
+
+            res.entry(label.clone()).or_default().push(Annotation::new(
+                label,
+                1.0,
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
+                    width,
+                    height,
+                )),
+            ));
+        }
+
+        res
+    }
+}
+
+impl Schema for Cityscapes<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: CityscapesOptions = self.config.options("cityscapes")?;
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each city and collect the annotation of each
+        // image into a linear stream, in the order of the recordings.
+        let mut datastreams = Vec::new();
+
+        for (city, entries) in self.entries(&options)? {
+            let mut frames = Vec::new();
+
+            for (index, entry) in entries.iter().enumerate() {
+                let polygons = self.load(&entry.path)?;
+
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(
+                    String::from("cam::left"),
+                    Some(self.image(&city, entry, &polygons)),
+                );
+
+                record.annotations = self.annotations(&polygons.objects, &options);
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", city));
+            datastreams.push((city, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CityscapesDebug {
+    msg: String,
+}
+
+impl From<&str> for CityscapesDebug {
+    fn from(msg: &str) -> Self {
+        CityscapesDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CityscapesDebug {
+    fn from(msg: String) -> Self {
+        CityscapesDebug { msg }
+    }
+}
+
+impl fmt::Display for CityscapesDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: cityscapes: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CityscapesError {
+    msg: String,
+}
+
+impl From<&str> for CityscapesError {
+    fn from(msg: &str) -> Self {
+        CityscapesError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CityscapesError {
+    fn from(msg: String) -> Self {
+        CityscapesError { msg }
+    }
+}
+
+impl fmt::Display for CityscapesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cityscapes: {}", self.msg)
+    }
+}
+
+impl Error for CityscapesError {}
@@ -0,0 +1,78 @@
+use serde::Deserialize;
+
+/// The set of instance classes of Cityscapes.
+const INSTANCES: &[&str] = &[
+    "person",
+    "rider",
+    "car",
+    "truck",
+    "bus",
+    "caravan",
+    "trailer",
+    "train",
+    "motorcycle",
+    "bicycle",
+];
+
+/// The handling of group labels (e.g., `cargroup`).
+///
+/// A group label marks a crowd of objects that could not be separated (e.g.,
+/// a row of parked cars) and, as such, its box does not match one object.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Groups {
+    /// Drop the group objects.
+    #[default]
+    Drop,
+    /// Keep the group objects with their group label (e.g., `cargroup`).
+    Keep,
+    /// Keep the group objects with the label of their class (e.g., `car`).
+    Merge,
+}
+
+/// The set of options applied to Cityscapes annotations.
+///
+/// By default, every split is imported and only the objects of the instance
+/// classes (e.g., `car`, `person`) are kept.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The set of labels to keep.
+    ///
+    /// A group label is kept if its class (e.g., `car` of `cargroup`) is kept.
+    pub labels: Option<Vec<String>>,
+    pub groups: Groups,
+    /// The set of splits (e.g., `train`, `val`) to import.
+    pub splits: Option<Vec<String>>,
+}
+
+impl Options {
+    /// Check if the split passes the set of filters.
+    pub fn keep_split(&self, split: &str) -> bool {
+        self.splits
+            .as_ref()
+            .map(|x| x.iter().any(|x| x == split))
+            .unwrap_or(true)
+    }
+
+    /// The label of an object, if kept.
+    pub fn label(&self, label: &str) -> Option<String> {
+        let (class, group) = match label.strip_suffix("group") {
+            Some(class) => (class, true),
+            None => (label, false),
+        };
+
+        let keep = match &self.labels {
+            Some(labels) => labels.iter().any(|x| x == class),
+            None => INSTANCES.contains(&class),
+        };
+
+        match (keep, group, &self.groups) {
+            (false, _, _) => None,
+            (true, false, _) => Some(label.to_string()),
+            (true, true, Groups::Drop) => None,
+            (true, true, Groups::Keep) => Some(label.to_string()),
+            (true, true, Groups::Merge) => Some(class.to_string()),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+/// A single object of a Cityscapes polygon annotation.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Object {
+    pub label: String,
+    /// The set of (x, y) vertices (in pixels).
+    pub polygon: Vec<[f64; 2]>,
+}
+
+impl Object {
+    /// The axis-aligned box about the polygon.
+    ///
+    /// xmin, ymin, xmax, ymax (in pixels)
+    pub fn bbox(&self) -> Option<[f64; 4]> {
+        if self.polygon.is_empty() {
+            return None;
+        }
+
+        let mut bbox = [
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ];
+
+        for [x, y] in self.polygon.iter() {
+            bbox[0] = bbox[0].min(*x);
+            bbox[1] = bbox[1].min(*y);
+            bbox[2] = bbox[2].max(*x);
+            bbox[3] = bbox[3].max(*y);
+        }
+
+        Some(bbox)
+    }
+}
+
+/// A `*_gtFine_polygons.json` annotation (i.e., one image).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Polygons {
+    pub img_width: u32,
+    pub img_height: u32,
+    pub objects: Vec<Object>,
+}
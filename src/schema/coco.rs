@@ -0,0 +1,278 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::annotation::Annotation as CocoAnnotation;
+use self::category::Category as CocoCategory;
+use self::image::Image as CocoImage;
+use self::video::Video as CocoVideo;
+
+mod annotation;
+mod category;
+mod image;
+mod video;
+
+type ImageId = u64;
+type CategoryId = u64;
+type VideoId = u64;
+
+/// The top-level layout of a COCO instances file.
+///
+/// The `videos` table is not part of the original COCO format but is commonly
+/// added by video extensions (e.g., YouTube-VIS, TAO) along with the
+/// `video_id` and `frame_id` fields of each image.
+#[derive(Debug, Deserialize)]
+struct Dataset {
+    images: Vec<CocoImage>,
+    #[serde(default)]
+    annotations: Vec<CocoAnnotation>,
+    categories: Vec<CocoCategory>,
+    #[serde(default)]
+    videos: Vec<CocoVideo>,
+}
+
+pub struct Coco<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Coco<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    /// Load the COCO instances file.
+    ///
+    /// This will read from a [`BufReader`] and deserialize the complete set of
+    /// tables at once, as COCO stores everything within a single file.
+    fn load(&self) -> Result<Dataset, Box<dyn Error>> {
+        let infile = File::open(&self.path).or(Err(Box::new(CocoError::from(format!(
+            "unable to open `{}`",
+            self.path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+        let data = serde_json::from_reader(reader)?;
+
+        self.debug(&format!(
+            "serde: deserialized data from `{}`",
+            self.path.display()
+        ));
+
+        Ok(data)
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", CocoDebug::from(msg));
+        }
+    }
+
+    /// The name of the stream for images not associated with a video.
+    ///
+    /// This defaults to the stem of the input file (e.g., `instances_val2017`).
+    fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("coco"))
+    }
+
+    fn image(&self, image: &CocoImage) -> Image {
+        let source = ImageSource::File(PathBuf::from(&image.file_name));
+        Image::new(source, image.width, image.height)
+    }
+
+    fn annotations(
+        &self,
+        annotations: &[&CocoAnnotation],
+        categories: &HashMap<CategoryId, CocoCategory>,
+    ) -> Result<HashMap<String, Vec<Annotation>>, Box<dyn Error>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for annotation in annotations {
+            let label = categories.get(&annotation.category_id).ok_or_else(|| {
+                CocoError::from(format!("unknown category `{}`", annotation.category_id))
+            })?;
+
+            // Convert the COCO bounding box into an axis-aligned region.
+            //
+            // COCO stores the box from its top-left corner whereas [`strem`]
+            // expects the box from its center, accordingly.
+            let [x, y, width, height] = annotation.bbox;
+
+            res.entry(label.name.clone())
+                .or_default()
+                .push(Annotation::new(
+                    label.name.clone(),
+                    annotation.score.unwrap_or(1.0),
+                    BoundingBox::AxisAligned(aa::Region::new(
+                        Point::new(x + (width / 2.0), y + (height / 2.0)),
+                        width,
+                        height,
+                    )),
+                ));
+        }
+
+        Ok(res)
+    }
+}
+
+impl Schema for Coco<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input file at `{}`", self.path.display()));
+
+        let dataset = self.load()?;
+
+        // Set up internal database.
+        //
+        // COCO relates annotations to images (and images to videos) by their
+        // identifiers; therefore, these relations must first be set up in
+        // order to import the streams linearly.
+        self.debug("building internal database");
+
+        let categories: HashMap<CategoryId, CocoCategory> =
+            dataset.categories.into_iter().map(|x| (x.id, x)).collect();
+
+        let videos: HashMap<VideoId, CocoVideo> =
+            dataset.videos.into_iter().map(|x| (x.id, x)).collect();
+
+        // There are multiple [`CocoAnnotation`] per image.
+        //
+        // Therefore, a mapping between an image and its associated set of
+        // [`CocoAnnotation`] must be created.
+        let mut annotations: HashMap<ImageId, Vec<&CocoAnnotation>> = HashMap::new();
+
+        for a in dataset.annotations.iter() {
+            annotations.entry(a.image_id).or_default().push(a);
+        }
+
+        // Group the images into streams.
+        //
+        // Images that belong to a video are grouped by the `video_id` whereas
+        // the remaining images are grouped into a single stream.
+        let mut groups: BTreeMap<Option<VideoId>, Vec<&CocoImage>> = BTreeMap::new();
+
+        for image in dataset.images.iter() {
+            groups.entry(image.video_id).or_default().push(image);
+        }
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each group and order the images by their
+        // frame (or image) identifier and file name to form a linear stream.
+        let mut datastreams = Vec::new();
+
+        for (video, mut images) in groups {
+            images.sort_by(|a, b| {
+                a.frame_id
+                    .unwrap_or(a.id)
+                    .cmp(&b.frame_id.unwrap_or(b.id))
+                    .then_with(|| a.file_name.cmp(&b.file_name))
+            });
+
+            let name = match video {
+                Some(id) => videos
+                    .get(&id)
+                    .and_then(|x| x.name.clone())
+                    .unwrap_or_else(|| id.to_string()),
+                None => self.name(),
+            };
+
+            let mut frames = Vec::new();
+
+            for (index, image) in images.into_iter().enumerate() {
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(String::from("cam"), Some(self.image(image)));
+
+                if let Some(annotations) = annotations.get(&image.id) {
+                    record.annotations = self.annotations(annotations, &categories)?;
+                }
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CocoDebug {
+    msg: String,
+}
+
+impl From<&str> for CocoDebug {
+    fn from(msg: &str) -> Self {
+        CocoDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CocoDebug {
+    fn from(msg: String) -> Self {
+        CocoDebug { msg }
+    }
+}
+
+impl fmt::Display for CocoDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: coco: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CocoError {
+    msg: String,
+}
+
+impl From<&str> for CocoError {
+    fn from(msg: &str) -> Self {
+        CocoError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CocoError {
+    fn from(msg: String) -> Self {
+        CocoError { msg }
+    }
+}
+
+impl fmt::Display for CocoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "coco: {}", self.msg)
+    }
+}
+
+impl Error for CocoError {}
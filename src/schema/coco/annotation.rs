@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Annotation {
+    pub image_id: u64,
+    pub category_id: u64,
+    /// x, y, width, height (from the top-left corner)
+    pub bbox: [f64; 4],
+    /// Only present in detection result files.
+    pub score: Option<f64>,
+}
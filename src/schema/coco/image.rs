@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Image {
+    pub id: u64,
+    pub file_name: String,
+    pub width: u32,
+    pub height: u32,
+    pub video_id: Option<u64>,
+    pub frame_id: Option<u64>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Video {
+    pub id: u64,
+    pub name: Option<String>,
+}
@@ -0,0 +1,413 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::options::{Column as CsvColumn, Options as CsvOptions};
+use self::table::Table as CsvTable;
+
+mod options;
+pub(super) mod table;
+
+type StreamKey = String;
+type FrameKey = String;
+type Channel = String;
+
+/// A single detection (i.e., row) of a file.
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    stream: StreamKey,
+    frame: FrameKey,
+    channel: Channel,
+    label: String,
+    score: f64,
+    /// x, y, width, height (from the center, in pixels)
+    bbox: [f64; 4],
+    /// The path to the image and its dimensions, if known.
+    image: Option<(PathBuf, Option<(u32, u32)>)>,
+}
+
+/// The positions of the mapped columns of a file.
+struct Layout {
+    stream: Option<usize>,
+    frame: usize,
+    channel: Option<usize>,
+    label: usize,
+    score: Option<usize>,
+    bbox: [usize; 4],
+    image: Option<usize>,
+    width: Option<usize>,
+    height: Option<usize>,
+}
+
+impl Layout {
+    fn new(table: &CsvTable, options: &CsvOptions) -> Result<Self, Box<dyn Error>> {
+        let columns = &options.columns;
+        let optional = |x: &Option<CsvColumn>| x.as_ref().map(|x| table.index(x)).transpose();
+
+        Ok(Layout {
+            stream: optional(&columns.stream)?,
+            frame: table.index(&columns.frame)?,
+            channel: optional(&columns.channel)?,
+            label: table.index(&columns.label)?,
+            score: optional(&columns.score)?,
+            bbox: [
+                table.index(&columns.bbox[0])?,
+                table.index(&columns.bbox[1])?,
+                table.index(&columns.bbox[2])?,
+                table.index(&columns.bbox[3])?,
+            ],
+            image: optional(&columns.image)?,
+            width: optional(&columns.width)?,
+            height: optional(&columns.height)?,
+        })
+    }
+}
+
+/// Compare two frame keys.
+///
+/// The numeric keys (e.g., frame numbers or timestamps) are compared by value
+/// and precede the remaining keys, which are compared as text.
+fn order(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.total_cmp(&y).then_with(|| a.cmp(b)),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// A generic set of detections in delimited text files (e.g., CSV or TSV).
+///
+/// The input may either be a single file or a directory of `.csv` and `.tsv`
+/// files. The columns are mapped through the options and each distinct stream
+/// key (or, if not mapped, each file) forms a stream.
+pub struct Csv<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Csv<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", CsvDebug::from(msg));
+        }
+    }
+
+    /// The set of files to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file()
+                && path
+                    .extension()
+                    .map(|x| x == "csv" || x == "tsv")
+                    .unwrap_or(false)
+            {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Load the set of [`Row`] of a file.
+    fn load(&self, path: &Path, options: &CsvOptions) -> Result<Vec<Row>, Box<dyn Error>> {
+        let data = fs::read_to_string(path).or(Err(Box::new(CsvError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let delimiter = options.delimiter.unwrap_or_else(|| {
+            match path.extension().map(|x| x == "tsv").unwrap_or(false) {
+                true => '\t',
+                false => ',',
+            }
+        });
+
+        let table = CsvTable::parse(&data, delimiter, options.header);
+        let layout = Layout::new(&table, options)
+            .map_err(|e| CsvError::from(format!("{}: {}", path.display(), e)))?;
+
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let mut rows = Vec::new();
+
+        for (line, fields) in table.rows.iter() {
+            let row = self
+                .row(fields, &layout, &name, dir, options)
+                .map_err(|e| CsvError::from(format!("{}:{}: {}", path.display(), line, e)))?;
+
+            rows.push(row);
+        }
+
+        self.debug(&format!(
+            "read {} rows from `{}`",
+            rows.len(),
+            path.display()
+        ));
+
+        Ok(rows)
+    }
+
+    /// Interpret the fields of a single row.
+    fn row(
+        &self,
+        fields: &[String],
+        layout: &Layout,
+        name: &str,
+        dir: &Path,
+        options: &CsvOptions,
+    ) -> Result<Row, Box<dyn Error>> {
+        let field = |i: usize| {
+            fields
+                .get(i)
+                .map(|x| x.as_str())
+                .ok_or_else(|| format!("missing field {}", i))
+        };
+
+        let optional = |i: Option<usize>| i.map(field).transpose();
+
+        let mut bbox = [0.0; 4];
+
+        for (value, i) in bbox.iter_mut().zip(layout.bbox) {
+            *value = field(i)?.parse()?;
+        }
+
+        let image = optional(layout.image)?
+            .filter(|x| !x.is_empty())
+            .map(|x| dir.join(x));
+
+        let mut dimensions = match (optional(layout.width)?, optional(layout.height)?) {
+            (Some(width), Some(height)) => Some((width.parse()?, height.parse()?)),
+            _ => options.dimensions,
+        };
+
+        if options.normalized {
+            // The dimensions are otherwise read from the image, if any.
+            if let (None, Some(path)) = (dimensions, &image) {
+                let size = imagesize::size(path)
+                    .map_err(|e| format!("unable to read `{}`: {}", path.display(), e))?;
+
+                dimensions = Some((size.width as u32, size.height as u32));
+            }
+
+            let (width, height) = dimensions.ok_or("missing image dimensions")?;
+
+            bbox[0] *= width as f64;
+            bbox[1] *= height as f64;
+            bbox[2] *= width as f64;
+            bbox[3] *= height as f64;
+        }
+
+        Ok(Row {
+            stream: optional(layout.stream)?.unwrap_or(name).to_string(),
+            frame: field(layout.frame)?.to_string(),
+            channel: optional(layout.channel)?
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| options.channel.clone()),
+            label: field(layout.label)?.to_string(),
+            score: match optional(layout.score)? {
+                Some(score) => score.parse()?,
+                None => 1.0,
+            },
+            bbox: options.format.center(bbox),
+            image: image.map(|x| (x, dimensions)),
+        })
+    }
+
+    /// Reference the image of a row, if any.
+    ///
+    /// If no dimensions are provided, then they are read from the image.
+    fn image(&self, row: &Row) -> Option<Image> {
+        let (path, dimensions) = row.image.as_ref()?;
+
+        let (width, height) = match dimensions {
+            Some(dimensions) => *dimensions,
+            None => {
+                let size = imagesize::size(path).ok()?;
+                (size.width as u32, size.height as u32)
+            }
+        };
+
+        Some(Image::new(ImageSource::File(path.clone()), width, height))
+    }
+
+    fn annotations(&self, rows: &[Row]) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for row in rows.iter() {
+            let [x, y, width, height] = row.bbox;
+
+            res.entry(row.label.clone())
+                .or_default()
+                .push(Annotation::new(
+                    row.label.clone(),
+                    row.score,
+                    BoundingBox::AxisAligned(aa::Region::new(Point::new(x, y), width, height)),
+                ));
+        }
+
+        res
+    }
+}
+
+impl Schema for Csv<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: CsvOptions = self.config.options("csv")?;
+
+        // Set up internal database.
+        //
+        // The rows are grouped by stream, frame, and channel such that the
+        // rows of a stream may be spread across files.
+        let mut streams: BTreeMap<StreamKey, HashMap<FrameKey, BTreeMap<Channel, Vec<Row>>>> =
+            BTreeMap::new();
+        let mut channels: HashMap<StreamKey, BTreeSet<Channel>> = HashMap::new();
+
+        for path in self.files()? {
+            for row in self.load(&path, &options)? {
+                channels
+                    .entry(row.stream.clone())
+                    .or_default()
+                    .insert(row.channel.clone());
+
+                streams
+                    .entry(row.stream.clone())
+                    .or_default()
+                    .entry(row.frame.clone())
+                    .or_default()
+                    .entry(row.channel.clone())
+                    .or_default()
+                    .push(row);
+            }
+        }
+
+        // Construct the set of [`Frame`].
+        //
+        // Each frame contains a record for every channel of its stream such
+        // that a channel without detections is kept as an empty record.
+        let mut datastreams = Vec::new();
+
+        for (name, mut data) in streams {
+            let mut keys = data.keys().cloned().collect::<Vec<FrameKey>>();
+            keys.sort_by(|a, b| order(a, b));
+
+            let mut frames = Vec::new();
+
+            for (index, key) in keys.iter().enumerate() {
+                let mut frame = Frame::new(index);
+                let mut rows = data.remove(key).unwrap_or_default();
+
+                for channel in channels[&name].iter() {
+                    let rows = rows.remove(channel).unwrap_or_default();
+
+                    let mut record = DetectionRecord::new(
+                        channel.clone(),
+                        rows.first().and_then(|x| self.image(x)),
+                    );
+
+                    record.annotations = self.annotations(&rows);
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CsvDebug {
+    msg: String,
+}
+
+impl From<&str> for CsvDebug {
+    fn from(msg: &str) -> Self {
+        CsvDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CsvDebug {
+    fn from(msg: String) -> Self {
+        CsvDebug { msg }
+    }
+}
+
+impl fmt::Display for CsvDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: csv: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CsvError {
+    msg: String,
+}
+
+impl From<&str> for CsvError {
+    fn from(msg: &str) -> Self {
+        CsvError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CsvError {
+    fn from(msg: String) -> Self {
+        CsvError { msg }
+    }
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "csv: {}", self.msg)
+    }
+}
+
+impl Error for CsvError {}
@@ -0,0 +1,125 @@
+use serde::Deserialize;
+
+/// A reference to a column, either by name (i.e., from the header) or by its
+/// (zero-based) position.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl Column {
+    fn name(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+/// The convention of the box columns.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// xmin, ymin, xmax, ymax
+    #[default]
+    Xyxy,
+    /// xmin, ymin, width, height
+    Xywh,
+    /// x, y, width, height (from the center)
+    Cxcywh,
+}
+
+impl Format {
+    /// Convert a box into x, y, width, height (from the center).
+    pub fn center(&self, [a, b, c, d]: [f64; 4]) -> [f64; 4] {
+        match self {
+            Format::Xyxy => [(a + c) / 2.0, (b + d) / 2.0, c - a, d - b],
+            Format::Xywh => [a + (c / 2.0), b + (d / 2.0), c, d],
+            Format::Cxcywh => [a, b, c, d],
+        }
+    }
+}
+
+/// The mapping of the fields of a detection to columns.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Columns {
+    /// The stream of the detection.
+    ///
+    /// If not provided, each file forms its own stream.
+    pub stream: Option<Column>,
+    /// The frame of the detection (e.g., a frame number or timestamp).
+    pub frame: Column,
+    /// The channel of the detection.
+    ///
+    /// If not provided, every detection belongs to the default channel.
+    pub channel: Option<Column>,
+    pub label: Column,
+    /// The score of the detection, which defaults to `1.0`.
+    pub score: Option<Column>,
+    /// The four columns of the box, following the [`Format`].
+    #[serde(rename = "box")]
+    pub bbox: [Column; 4],
+    /// The path to the image (relative to the file).
+    pub image: Option<Column>,
+    /// The dimensions of the image (in pixels).
+    pub width: Option<Column>,
+    pub height: Option<Column>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            stream: None,
+            frame: Column::name("frame"),
+            channel: None,
+            label: Column::name("label"),
+            score: None,
+            bbox: [
+                Column::name("xmin"),
+                Column::name("ymin"),
+                Column::name("xmax"),
+                Column::name("ymax"),
+            ],
+            image: None,
+            width: None,
+            height: None,
+        }
+    }
+}
+
+/// The set of options applied to CSV/TSV detections.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The delimiter of the fields.
+    ///
+    /// If not provided, a tab is used for `.tsv` files and a comma otherwise.
+    pub delimiter: Option<char>,
+    /// The first line of each file is a header (i.e., the column names).
+    pub header: bool,
+    pub columns: Columns,
+    pub format: Format,
+    /// The box is normalized by the dimensions of the image (i.e., `[0, 1]`).
+    pub normalized: bool,
+    /// The dimensions (width, height) of every image.
+    ///
+    /// This is only used when no `width` and `height` columns are provided;
+    /// otherwise, the dimensions are read from the image, if any.
+    pub dimensions: Option<(u32, u32)>,
+    /// The channel of the detections without a channel column.
+    pub channel: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            header: true,
+            columns: Columns::default(),
+            format: Format::default(),
+            normalized: false,
+            dimensions: None,
+            channel: String::from("cam"),
+        }
+    }
+}
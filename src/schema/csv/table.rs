@@ -0,0 +1,81 @@
+use std::error::Error;
+
+use super::options::Column;
+
+/// Split a single line into its fields.
+///
+/// A field may be quoted (i.e., `"a,b"`) to include the delimiter, where a
+/// quote is escaped by another quote (i.e., `""`). A quoted field may not span
+/// multiple lines.
+pub fn fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut res = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => res.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    res.push(field);
+    res.into_iter().map(|x| x.trim().to_string()).collect()
+}
+
+/// A delimited text file (e.g., CSV or TSV).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    pub header: Option<Vec<String>>,
+    /// The set of rows, with their (one-based) line number.
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl Table {
+    pub fn parse(data: &str, delimiter: char, header: bool) -> Self {
+        let mut lines = data
+            .lines()
+            .enumerate()
+            .filter(|(_, x)| !x.trim().is_empty())
+            .map(|(i, x)| (i + 1, fields(x, delimiter)));
+
+        let header = if header {
+            lines.next().map(|(_, x)| x)
+        } else {
+            None
+        };
+
+        Table {
+            header,
+            rows: lines.collect(),
+        }
+    }
+
+    /// Resolve the position of a column.
+    pub fn index(&self, column: &Column) -> Result<usize, Box<dyn Error>> {
+        match column {
+            Column::Index(i) => Ok(*i),
+            Column::Name(name) => self
+                .header
+                .as_ref()
+                .ok_or_else(|| format!("column `{}` requires a header", name))?
+                .iter()
+                .position(|x| x == name)
+                .ok_or_else(|| format!("missing column `{}`", name).into()),
+        }
+    }
+
+    /// Find the position of a column by name, ignoring case.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header
+            .as_ref()?
+            .iter()
+            .position(|x| x.eq_ignore_ascii_case(name))
+    }
+}
@@ -0,0 +1,226 @@
+use nalgebra::{
+    Quaternion, RowSVector as StaticRowVector, SMatrix as StaticMatrix, SVector as StaticVector,
+    Translation, UnitQuaternion,
+};
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+
+/// A 3D bounding box.
+///
+/// This is shared by the schemas that provide 3D annotations which must be
+/// projected onto a camera to be used by [`strem`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cuboid {
+    /// x, y, z
+    pub translation: [f64; 3],
+    /// width, length, height
+    pub size: [f64; 3],
+    /// w, x, y, z
+    pub rotation: [f64; 4],
+}
+
+impl Cuboid {
+    pub fn new(translation: [f64; 3], size: [f64; 3], rotation: [f64; 4]) -> Self {
+        Self {
+            translation,
+            size,
+            rotation,
+        }
+    }
+
+    /// Compute the corners of the box in 3D space.
+    ///
+    /// This function has been translated from the original implementation found
+    /// [here](https://github.com/lyft/nuscenes-devkit/blob/master/lyft_dataset_sdk/utils/data_classes.py#L622).
+    fn corners(&self) -> StaticMatrix<f64, 3, 8> {
+        let [width, length, height] = self.size;
+
+        // Construct the correctly scaled box about the origin (0, 0, 0) in the
+        // 3D coordinate plane.
+        //
+        // The representation of the box is a 3x8 matrix where each row
+        // corresponds to the x, y, and z direction, respectively. Therefore,
+        // the unit box can be represented as the following 8 points:
+        //
+        // x: [1, 1, 1, 1, -1, -1, -1, -1]
+        // y: [1, -1, -1, 1, 1, -1, -1, 1]
+        // z: [1, 1, -1, -1, 1, 1, -1, -1]
+        let xs: StaticRowVector<f64, 8> =
+            (length / 2.0) * StaticRowVector::from([1.0, 1.0, 1.0, 1.0, -1.0, -1.0, -1.0, -1.0]);
+        let ys: StaticRowVector<f64, 8> =
+            (width / 2.0) * StaticRowVector::from([1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, 1.0]);
+        let zs: StaticRowVector<f64, 8> =
+            (height / 2.0) * StaticRowVector::from([1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0]);
+
+        let corners: StaticMatrix<f64, 3, 8> = StaticMatrix::from_rows(&[xs, ys, zs]);
+
+        // Rotate the box based on the annotation rotation. This rotation is with
+        // respect to the global coordinate system.
+        //
+        // The correct operation to perform here is a matrix multiplication. Not
+        // a dot product.
+        let [w, i, j, k] = self.rotation;
+        let rotation: UnitQuaternion<f64> =
+            UnitQuaternion::from_quaternion(Quaternion::new(w, i, j, k));
+
+        let corners = rotation.to_rotation_matrix().matrix() * corners;
+
+        // Translate the box based on the annotation translation. This
+        // translation is with respect to the global coordinate system.
+        let [x, y, z] = self.translation;
+
+        let corners = StaticMatrix::from_rows(&[
+            corners.row(0).add_scalar(x),
+            corners.row(1).add_scalar(y),
+            corners.row(2).add_scalar(z),
+        ]);
+
+        corners
+    }
+
+    /// Translate (shift) [`Cuboid`] by [`Translation`] amount.
+    ///
+    /// This performs a component-wise scalar addition in the x, y, and z
+    /// direction, respectively.
+    fn translate(&mut self, translation: Translation<f64, 3>) {
+        let [x, y, z] = self.translation;
+
+        self.translation[0] = x + translation.x;
+        self.translation[1] = y + translation.y;
+        self.translation[2] = z + translation.z;
+    }
+
+    /// Rotate [`Cuboid`] by a [`UnitQuaternion`] amount.
+    ///
+    /// This rotates results in the [`Cuboid`] rotated about the origin of
+    /// the coordinate system (0, 0, 0).
+    fn rotate(&mut self, rotation: UnitQuaternion<f64>) {
+        let matrix = *rotation.to_rotation_matrix().matrix();
+        let translation = matrix * StaticVector::<f64, 3>::from(self.translation);
+
+        self.translation = translation
+            .iter()
+            .cloned()
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        let [w, i, j, k] = self.rotation;
+        let rotation = rotation * UnitQuaternion::from_quaternion(Quaternion::new(w, i, j, k));
+
+        let [x, y, z, w]: [f64; 4] = rotation
+            .as_vector()
+            .iter()
+            .cloned()
+            .collect::<Vec<f64>>()
+            .try_into()
+            .unwrap();
+
+        self.rotation = [w, x, y, z];
+    }
+
+    /// Perform a complete transformation (translation and rotation).
+    ///
+    /// This is a convenience function that performs both a translation and
+    /// rotation (in that order).
+    pub fn transform(
+        mut self,
+        translation: Translation<f64, 3>,
+        rotation: UnitQuaternion<f64>,
+    ) -> Self {
+        self.translate(translation);
+        self.rotate(rotation);
+
+        self
+    }
+
+    /// Move the [`Cuboid`] into the coordinate system of a pose.
+    ///
+    /// The pose (e.g., ego or sensor) is expressed with respect to the current
+    /// coordinate system of the [`Cuboid`]; therefore, the inverse of the pose
+    /// is applied, accordingly.
+    pub fn into_frame(self, translation: [f64; 3], rotation: [f64; 4]) -> Self {
+        let [x, y, z] = translation;
+        let [w, i, j, k] = rotation;
+
+        self.transform(
+            Translation::<f64, 3>::new(x, y, z).inverse(),
+            UnitQuaternion::from_quaternion(Quaternion::new(w, i, j, k)).inverse(),
+        )
+    }
+
+    /// Move the [`Cuboid`] out of the coordinate system of a pose.
+    ///
+    /// This is the inverse of [`Cuboid::into_frame`] where the pose is applied
+    /// directly (i.e., rotation and then translation).
+    pub fn out_of_frame(self, translation: [f64; 3], rotation: [f64; 4]) -> Self {
+        let [x, y, z] = translation;
+        let [w, i, j, k] = rotation;
+
+        self.transform(
+            Translation::<f64, 3>::identity(),
+            UnitQuaternion::from_quaternion(Quaternion::new(w, i, j, k)),
+        )
+        .transform(
+            Translation::<f64, 3>::new(x, y, z),
+            UnitQuaternion::identity(),
+        )
+    }
+
+    /// Check if the [`Cuboid`] is within the image.
+    ///
+    /// This procedure is ported from the NuScenes SDK provided. For more
+    /// information, see:
+    /// https://github.com/lyft/nuscenes-devkit/blob/49c36da0a85da6bc9e8f2a39d5d967311cd75069/lyft_dataset_sdk/utils/geometry_utils.py#L62
+    pub fn inside(&self, view: StaticMatrix<f64, 3, 3>, width: f64, height: f64) -> bool {
+        let corners = self.corners();
+        let projection = self.projection(view);
+
+        let visible = projection.row(0).iter().all(|x| *x > 0.0 && *x < width);
+        let visible = visible && projection.row(1).iter().all(|x| *x > 0.0 && *x < height);
+        let visible = visible && corners.row(2).iter().all(|x| *x > 1.0);
+
+        visible
+    }
+
+    /// Project the [`Cuboid`] onto a perspective and normalize.
+    ///
+    /// This projection does not modify the [`Cuboid`] but simply returns the
+    /// set of projected points representing the box.
+    pub fn projection(&self, view: StaticMatrix<f64, 3, 3>) -> StaticMatrix<f64, 3, 8> {
+        let corners = self.corners();
+
+        // project
+        let corners = view * corners;
+
+        // normalize
+        let corners = corners.component_div(&StaticMatrix::<f64, 3, 8>::from_rows(&[
+            StaticRowVector::<f64, 8>::from(corners.row(2)),
+            StaticRowVector::<f64, 8>::from(corners.row(2)),
+            StaticRowVector::<f64, 8>::from(corners.row(2)),
+        ]));
+
+        corners
+    }
+
+    /// Project the [`Cuboid`] onto a perspective as an axis-aligned region.
+    ///
+    /// The region is the tightest box about the set of projected corners.
+    pub fn region(&self, view: StaticMatrix<f64, 3, 3>) -> aa::Region {
+        let m = self.projection(view);
+
+        let xmin = m.row(0).iter().copied().fold(f64::NAN, f64::min);
+        let ymin = m.row(1).iter().copied().fold(f64::NAN, f64::min);
+
+        let xmax = m.row(0).iter().copied().fold(f64::NAN, f64::max);
+        let ymax = m.row(1).iter().copied().fold(f64::NAN, f64::max);
+
+        let width = xmax - xmin;
+        let height = ymax - ymin;
+
+        aa::Region::new(
+            Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
+            width,
+            height,
+        )
+    }
+}
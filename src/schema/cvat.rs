@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::{Metadata, Schema};
+
+use self::annotations::{Annotations as CvatAnnotations, Task as CvatTask};
+use self::options::Options as CvatOptions;
+use self::track::{Shape as CvatShape, Track as CvatTrack};
+
+mod annotations;
+mod options;
+mod track;
+
+type FrameId = usize;
+type TrackId = String;
+
+/// The CVAT for video 1.1 XML export.
+///
+/// The input may either be a single export (e.g., `annotations.xml`) or a
+/// directory of exports. Each task forms a stream where the track identifiers
+/// of each frame are provided as stream-level metadata.
+pub struct Cvat<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Cvat<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", CvatDebug::from(msg));
+        }
+    }
+
+    /// Load the [`CvatAnnotations`] of an export.
+    fn load(&self, path: &Path) -> Result<CvatAnnotations, Box<dyn Error>> {
+        let data = fs::read_to_string(path).or(Err(Box::new(CvatError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let annotations = data
+            .parse()
+            .map_err(|e| CvatError::from(format!("{}: {}", path.display(), e)))?;
+
+        self.debug(&format!("read export from `{}`", path.display()));
+
+        Ok(annotations)
+    }
+
+    /// The set of exports to import.
+    ///
+    /// Within a directory, either the XML files or the `annotations.xml` of
+    /// each (unpacked) export are collected.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "xml").unwrap_or(false) {
+                files.push(path);
+            } else if path.join("annotations.xml").is_file() {
+                files.push(path.join("annotations.xml"));
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// The name of the stream of a task.
+    ///
+    /// The (free-form) task name is neither unique across exports nor a valid
+    /// file name; therefore, the stream is named after the export and the
+    /// identifier of the task, instead.
+    fn name(&self, path: &Path, task: &CvatTask) -> String {
+        let mut export = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+        // The unpacked exports are all named `annotations.xml`.
+        if export == "annotations" {
+            if let Some(name) = path.parent().and_then(|x| x.file_name()) {
+                export = name.to_string_lossy().into_owned();
+            }
+        }
+
+        if task.id == export {
+            export
+        } else {
+            format!("{}-{}", export, task.id)
+        }
+    }
+
+    /// Reference the extracted frame of a task, if any.
+    ///
+    /// The frames follow the naming of CVAT when exporting with images.
+    fn image(&self, dir: &Path, task: &CvatTask, index: FrameId) -> Option<Image> {
+        let path = dir.join(format!("frame_{:06}.PNG", index));
+
+        if !path.is_file() {
+            return None;
+        }
+
+        let (width, height) = match task.dimensions {
+            Some(dimensions) => dimensions,
+            None => {
+                let size = imagesize::size(&path).ok()?;
+                (size.width as u32, size.height as u32)
+            }
+        };
+
+        Some(Image::new(ImageSource::File(path), width, height))
+    }
+
+    fn annotation(&self, track: &CvatTrack, shape: &CvatShape) -> Annotation {
+        let [xtl, ytl, xbr, ybr] = shape.bbox;
+
+        let width = xbr - xtl;
+        let height = ybr - ytl;
+
+        Annotation::new(
+            track.label.clone(),
+            1.0,
+            BoundingBox::AxisAligned(aa::Region::new(
+                Point::new(xtl + (width / 2.0), ytl + (height / 2.0)),
+                width,
+                height,
+            )),
+        )
+    }
+}
+
+impl Schema for Cvat<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        Ok(self.import_with_metadata()?.0)
+    }
+
+    fn import_with_metadata(
+        &self,
+    ) -> Result<(Vec<(String, Vec<Frame>)>, Metadata), Box<dyn Error>> {
+        let mut metadata = Metadata::new();
+
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: CvatOptions = self.config.options("cvat")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let annotations = self.load(&path)?;
+
+            let images = options.images.clone().unwrap_or_else(|| {
+                path.parent()
+                    .map(|x| x.join("images"))
+                    .unwrap_or_else(|| PathBuf::from("images"))
+            });
+
+            // An export without any task (e.g., a hand-written file) forms a
+            // single task named after the file.
+            let mut tasks = annotations.tasks.clone();
+
+            if tasks.is_empty() {
+                let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+                tasks.push(CvatTask {
+                    id: stem.clone(),
+                    name: stem,
+                    size: None,
+                    dimensions: None,
+                });
+            }
+
+            for task in tasks.iter() {
+                // Collect the set of tracks of the task.
+                //
+                // Only project exports associate a track with its task;
+                // otherwise, the export is of a single task.
+                let tracks = annotations
+                    .tracks
+                    .iter()
+                    .filter(|x| match &x.task {
+                        Some(id) => *id == task.id,
+                        None => tasks.len() == 1,
+                    })
+                    .collect::<Vec<&CvatTrack>>();
+
+                let length = task.size.unwrap_or_else(|| {
+                    tracks
+                        .iter()
+                        .filter_map(|x| x.shapes.last())
+                        .map(|x| x.frame + 1)
+                        .max()
+                        .unwrap_or(0)
+                });
+
+                let expanded = tracks
+                    .iter()
+                    .map(|x| (*x, x.expand(length)))
+                    .collect::<Vec<(&CvatTrack, BTreeMap<FrameId, CvatShape>)>>();
+
+                // Construct the set of [`Frame`].
+                //
+                // Each frame of the task is inserted such that no gaps exist.
+                // The track identifiers are recorded in the same order as the
+                // annotations of each label.
+                let mut frames = Vec::new();
+                let mut ids: BTreeMap<FrameId, BTreeMap<String, Vec<TrackId>>> = BTreeMap::new();
+
+                for index in 0..length {
+                    let mut frame = Frame::new(index);
+                    let mut record =
+                        DetectionRecord::new(String::from("cam"), self.image(&images, task, index));
+
+                    for (track, shapes) in expanded.iter() {
+                        if let Some(shape) = shapes.get(&index).filter(|x| options.keep(x)) {
+                            record
+                                .annotations
+                                .entry(track.label.clone())
+                                .or_default()
+                                .push(self.annotation(track, shape));
+
+                            ids.entry(index)
+                                .or_default()
+                                .entry(track.label.clone())
+                                .or_default()
+                                .push(track.id.clone());
+                        }
+                    }
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                    frames.push(frame);
+                }
+
+                let name = self.name(&path, task);
+
+                metadata.insert(
+                    name.clone(),
+                    serde_json::json!({ "task": task.id, "name": task.name, "tracks": ids }),
+                );
+
+                self.debug(&format!("imported stream `{}`", name));
+                datastreams.push((name, frames));
+            }
+        }
+
+        Ok((datastreams, metadata))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CvatDebug {
+    msg: String,
+}
+
+impl From<&str> for CvatDebug {
+    fn from(msg: &str) -> Self {
+        CvatDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CvatDebug {
+    fn from(msg: String) -> Self {
+        CvatDebug { msg }
+    }
+}
+
+impl fmt::Display for CvatDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: cvat: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CvatError {
+    msg: String,
+}
+
+impl From<&str> for CvatError {
+    fn from(msg: &str) -> Self {
+        CvatError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for CvatError {
+    fn from(msg: String) -> Self {
+        CvatError { msg }
+    }
+}
+
+impl fmt::Display for CvatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cvat: {}", self.msg)
+    }
+}
+
+impl Error for CvatError {}
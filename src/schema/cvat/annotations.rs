@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use roxmltree::{Document, Node};
+
+use super::track::Track;
+
+/// A CVAT task (or job) of an export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Task {
+    pub id: String,
+    pub name: String,
+    /// The number of frames, if provided.
+    pub size: Option<usize>,
+    /// width, height (in pixels)
+    pub dimensions: Option<(u32, u32)>,
+}
+
+/// A CVAT for video 1.1 export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotations {
+    pub tasks: Vec<Task>,
+    pub tracks: Vec<Track>,
+}
+
+/// Find the first direct child element by name.
+pub fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|x| x.is_element() && x.has_tag_name(name))
+}
+
+/// Find the set of direct child elements by name.
+pub fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |x| x.is_element() && x.has_tag_name(name))
+}
+
+/// Find the (trimmed) text of the first direct child element by name.
+pub fn text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|x| x.text()).map(|x| x.trim())
+}
+
+/// Parse the `<original_size>` of an element, if any.
+fn dimensions(node: Node) -> Result<Option<(u32, u32)>, Box<dyn Error>> {
+    match child(node, "original_size") {
+        Some(size) => Ok(Some((
+            text(size, "width").ok_or("missing `width`")?.parse()?,
+            text(size, "height").ok_or("missing `height`")?.parse()?,
+        ))),
+        None => Ok(None),
+    }
+}
+
+impl Task {
+    /// Parse the task from a `<task>` (or `<job>`) element of the `<meta>`.
+    ///
+    /// Older exports provide the `<original_size>` as part of the `<meta>`
+    /// rather than the task; therefore, a fallback may be provided.
+    fn from_node(node: Node, fallback: Option<(u32, u32)>) -> Result<Self, Box<dyn Error>> {
+        let id = text(node, "id").ok_or("missing task `id`")?.to_string();
+
+        Ok(Task {
+            name: text(node, "name").unwrap_or(&id).to_string(),
+            size: text(node, "size").map(|x| x.parse::<usize>()).transpose()?,
+            dimensions: dimensions(node)?.or(fallback),
+            id,
+        })
+    }
+}
+
+impl FromStr for Annotations {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let document = Document::parse(s)?;
+        let root = document.root_element();
+
+        // Collect the set of tasks from the `<meta>`.
+        //
+        // A task export provides a single `<task>`, a job export provides a
+        // single `<job>`, and a project export provides each of its tasks.
+        let mut tasks = Vec::new();
+
+        if let Some(meta) = child(root, "meta") {
+            let fallback = dimensions(meta)?;
+
+            let mut nodes = children(meta, "task")
+                .chain(children(meta, "job"))
+                .collect::<Vec<Node>>();
+
+            if let Some(project) = child(meta, "project") {
+                if let Some(list) = child(project, "tasks") {
+                    nodes.extend(children(list, "task"));
+                }
+            }
+
+            for node in nodes {
+                tasks.push(Task::from_node(node, fallback)?);
+            }
+        }
+
+        let mut tracks = Vec::new();
+
+        for node in children(root, "track") {
+            if let Some(track) = Track::from_node(node)? {
+                tracks.push(track);
+            }
+        }
+
+        Ok(Annotations { tasks, tracks })
+    }
+}
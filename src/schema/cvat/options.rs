@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use super::track::Shape;
+
+/// The set of options applied to CVAT exports.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// Keep objects marked as `occluded`.
+    pub occluded: bool,
+    /// The directory of the extracted frames (i.e., `frame_000000.PNG`).
+    ///
+    /// If not provided, the `images` directory next to the export is used.
+    pub images: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            occluded: true,
+            images: None,
+        }
+    }
+}
+
+impl Options {
+    /// Check if the [`Shape`] passes the set of filters.
+    pub fn keep(&self, shape: &Shape) -> bool {
+        !shape.occluded || self.occluded
+    }
+}
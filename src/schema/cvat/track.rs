@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use roxmltree::Node;
+
+use super::annotations::children;
+
+/// A single `<box>` of a track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    pub frame: usize,
+    pub outside: bool,
+    pub occluded: bool,
+    pub keyframe: bool,
+    /// xtl, ytl, xbr, ybr (in pixels)
+    pub bbox: [f64; 4],
+}
+
+/// A single `<track>` of boxes.
+///
+/// The tracks of any other shape (e.g., polygons, points) are not supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub id: String,
+    pub label: String,
+    /// Only present in project exports.
+    pub task: Option<String>,
+    pub shapes: Vec<Shape>,
+}
+
+/// Find the value of an attribute by name.
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, Box<dyn Error>> {
+    Ok(node
+        .attribute(name)
+        .ok_or_else(|| format!("missing attribute `{}`", name))?)
+}
+
+/// Interpret a `0`/`1` attribute, where a missing attribute is not set.
+fn flag(node: Node, name: &str) -> bool {
+    matches!(node.attribute(name), Some("1"))
+}
+
+impl Shape {
+    fn from_node(node: Node) -> Result<Self, Box<dyn Error>> {
+        Ok(Shape {
+            frame: attribute(node, "frame")?.parse()?,
+            outside: flag(node, "outside"),
+            occluded: flag(node, "occluded"),
+            keyframe: flag(node, "keyframe"),
+            bbox: [
+                attribute(node, "xtl")?.parse()?,
+                attribute(node, "ytl")?.parse()?,
+                attribute(node, "xbr")?.parse()?,
+                attribute(node, "ybr")?.parse()?,
+            ],
+        })
+    }
+
+    /// Linearly interpolate between two shapes at a frame.
+    fn interpolate(&self, other: &Shape, frame: usize) -> Shape {
+        let t = (frame - self.frame) as f64 / (other.frame - self.frame) as f64;
+
+        let mut bbox = self.bbox;
+
+        for (i, x) in bbox.iter_mut().enumerate() {
+            *x += (other.bbox[i] - *x) * t;
+        }
+
+        Shape {
+            frame,
+            outside: false,
+            occluded: self.occluded,
+            keyframe: false,
+            bbox,
+        }
+    }
+}
+
+impl Track {
+    /// Parse the track from a `<track>` element.
+    ///
+    /// If the track does not contain any `<box>`, then [`None`] is returned.
+    pub fn from_node(node: Node) -> Result<Option<Self>, Box<dyn Error>> {
+        let mut shapes = children(node, "box")
+            .map(Shape::from_node)
+            .collect::<Result<Vec<Shape>, _>>()?;
+
+        if shapes.is_empty() {
+            return Ok(None);
+        }
+
+        shapes.sort_by_key(|x| x.frame);
+
+        Ok(Some(Track {
+            id: attribute(node, "id")?.to_string(),
+            label: attribute(node, "label")?.to_string(),
+            task: node.attribute("task_id").map(|x| x.to_string()),
+            shapes,
+        }))
+    }
+
+    /// Expand the shapes of the track into a shape per frame.
+    ///
+    /// This follows the semantics of CVAT where (1) a shape persists until the
+    /// next shape (or the end of the task), (2) the frames between two shapes
+    /// are linearly interpolated, and (3) an `outside` shape ends the track
+    /// until the next shape, accordingly.
+    pub fn expand(&self, length: usize) -> BTreeMap<usize, Shape> {
+        let mut res = BTreeMap::new();
+
+        for (i, shape) in self.shapes.iter().enumerate() {
+            if shape.outside {
+                continue;
+            }
+
+            let next = self.shapes.get(i + 1);
+            let end = next.map(|x| x.frame).unwrap_or(length).min(length);
+
+            for frame in shape.frame..end {
+                let current = match next {
+                    Some(next) if !next.outside && frame != shape.frame => {
+                        shape.interpolate(next, frame)
+                    }
+                    _ => Shape {
+                        frame,
+                        keyframe: shape.keyframe && frame == shape.frame,
+                        ..shape.clone()
+                    },
+                };
+
+                res.insert(frame, current);
+            }
+        }
+
+        res
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::info::{Info as DairInfo, Pair as DairPair};
+use self::label::Label as DairLabel;
+use self::options::Options as DairOptions;
+
+mod info;
+mod label;
+mod options;
+
+type FrameId = String;
+
+/// The set of sides (i.e., the directory and its channel).
+const SIDES: &[(&str, &str)] = &[
+    ("vehicle-side", "cam::vehicle"),
+    ("infrastructure-side", "cam::infrastructure"),
+];
+
+/// The DAIR-V2X cooperative (vehicle-infrastructure) dataset.
+///
+/// The root is the `cooperative-vehicle-infrastructure` directory. Each pair
+/// of `cooperative/data_info.json` forms a [`Frame`] with one record for the
+/// vehicle-side camera and one for the infrastructure-side camera. The pairs
+/// of each (vehicle-side) batch form a stream, ordered by timestamp.
+pub struct DairV2x<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> DairV2x<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", DairDebug::from(msg));
+        }
+    }
+
+    /// Load a JSON file.
+    fn load<T>(&self, path: &Path) -> Result<T, Box<dyn Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let infile = File::open(path).or(Err(Box::new(DairError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let data = serde_json::from_reader(BufReader::new(infile))
+            .map_err(|e| DairError::from(format!("{}: {}", path.display(), e)))?;
+
+        Ok(data)
+    }
+
+    /// Load the `data_info.json` of a side, keyed by frame.
+    ///
+    /// If the side does not provide one, then the default layout of the side
+    /// is assumed for each frame, instead.
+    fn infos(&self, side: &str) -> Result<HashMap<FrameId, DairInfo>, Box<dyn Error>> {
+        let path = self.root.join(side).join("data_info.json");
+
+        if !path.is_file() {
+            return Ok(HashMap::new());
+        }
+
+        let infos: Vec<DairInfo> = self.load(&path)?;
+
+        self.debug(&format!(
+            "read {} frames from `{}`",
+            infos.len(),
+            path.display()
+        ));
+
+        Ok(infos
+            .into_iter()
+            .map(|x| (info::id(&x.image_path), x))
+            .collect())
+    }
+
+    /// Read the [`Image`] along with its dimensions from the file header.
+    ///
+    /// If the image is missing (or unreadable), then no [`Image`] is provided.
+    fn image(&self, path: PathBuf) -> Option<Image> {
+        let size = imagesize::size(&path).ok()?;
+        let source = ImageSource::File(path);
+
+        Some(Image::new(source, size.width as u32, size.height as u32))
+    }
+
+    /// Convert the set of [`DairLabel`] that pass the filters into annotations.
+    fn annotations(
+        &self,
+        labels: &[DairLabel],
+        options: &DairOptions,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for label in labels.iter().filter(|x| options.keep(x)) {
+            let [left, top, right, bottom] = label.bbox();
+
+            let width = right - left;
+            let height = bottom - top;
+
+            res.entry(label.kind.clone())
+                .or_default()
+                .push(Annotation::new(
+                    label.kind.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(aa::Region::new(
+                        Point::new(left + (width / 2.0), top + (height / 2.0)),
+                        width,
+                        height,
+                    )),
+                ));
+        }
+
+        res
+    }
+
+    /// Construct the record of one side of a pair.
+    fn record(
+        &self,
+        side: &str,
+        channel: &str,
+        image: &str,
+        infos: &HashMap<FrameId, DairInfo>,
+        options: &DairOptions,
+    ) -> Result<DetectionRecord, Box<dyn Error>> {
+        let id = info::id(image);
+
+        let path = match infos
+            .get(&id)
+            .and_then(|x| x.label_camera_std_path.as_ref())
+        {
+            Some(path) => self.root.join(side).join(path),
+            None => self
+                .root
+                .join(side)
+                .join("label")
+                .join("camera")
+                .join(format!("{}.json", id)),
+        };
+
+        let mut record =
+            DetectionRecord::new(channel.to_string(), self.image(self.root.join(image)));
+
+        // The test split does not provide labels.
+        //
+        // Therefore, a missing label file results in a record without
+        // annotations rather than an error.
+        if path.is_file() {
+            let labels: Vec<DairLabel> = self.load(&path)?;
+            record.annotations = self.annotations(&labels, options);
+        }
+
+        Ok(record)
+    }
+}
+
+impl Schema for DairV2x<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: DairOptions = self.config.options("dair-v2x")?;
+
+        let pairs: Vec<DairPair> =
+            self.load(&self.root.join("cooperative").join("data_info.json"))?;
+
+        let sides = SIDES
+            .iter()
+            .map(|(side, channel)| Ok((*side, *channel, self.infos(side)?)))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+        // Group the pairs into streams.
+        //
+        // The batch and timestamp of a pair are taken from its vehicle-side
+        // frame. If no batch is provided, then every pair forms one stream.
+        let mut streams: BTreeMap<String, Vec<(f64, &DairPair)>> = BTreeMap::new();
+
+        let vehicle = &sides[0].2;
+
+        for pair in pairs.iter() {
+            let info = vehicle.get(&info::id(&pair.vehicle_image_path));
+
+            let batch = info
+                .and_then(|x| x.batch_id.as_ref())
+                .map(|x| x.text())
+                .unwrap_or(String::from("cooperative"));
+
+            let timestamp = info
+                .and_then(|x| x.image_timestamp.as_ref())
+                .map(|x| x.value())
+                .unwrap_or(f64::NAN);
+
+            streams.entry(batch).or_default().push((timestamp, pair));
+        }
+
+        let mut datastreams = Vec::new();
+
+        for (name, mut pairs) in streams {
+            pairs.sort_by(|a, b| {
+                a.0.total_cmp(&b.0)
+                    .then_with(|| a.1.vehicle_image_path.cmp(&b.1.vehicle_image_path))
+            });
+
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each pair and create a record for both
+            // sides such that the viewpoints share the same [`Frame`].
+            let mut frames = Vec::new();
+
+            for (index, (_, pair)) in pairs.iter().enumerate() {
+                let mut frame = Frame::new(index);
+
+                let images = [&pair.vehicle_image_path, &pair.infrastructure_image_path];
+
+                for ((side, channel, infos), image) in sides.iter().zip(images) {
+                    let record = self.record(side, channel, image, infos, &options)?;
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DairDebug {
+    msg: String,
+}
+
+impl From<&str> for DairDebug {
+    fn from(msg: &str) -> Self {
+        DairDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for DairDebug {
+    fn from(msg: String) -> Self {
+        DairDebug { msg }
+    }
+}
+
+impl fmt::Display for DairDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: dair-v2x: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DairError {
+    msg: String,
+}
+
+impl From<&str> for DairError {
+    fn from(msg: &str) -> Self {
+        DairError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for DairError {
+    fn from(msg: String) -> Self {
+        DairError { msg }
+    }
+}
+
+impl fmt::Display for DairError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dair-v2x: {}", self.msg)
+    }
+}
+
+impl Error for DairError {}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// A number that may be encoded as a string (e.g., `"1626155123840389"`).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Number {
+    Float(f64),
+    Text(String),
+}
+
+impl Number {
+    pub fn value(&self) -> f64 {
+        match self {
+            Number::Float(x) => *x,
+            Number::Text(x) => x.trim().parse().unwrap_or(f64::NAN),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            Number::Float(x) => x.to_string(),
+            Number::Text(x) => x.clone(),
+        }
+    }
+}
+
+/// A single entry of `cooperative/data_info.json` (i.e., a pair of frames).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Pair {
+    /// The vehicle-side image (e.g., `vehicle-side/image/015344.jpg`).
+    pub vehicle_image_path: String,
+    /// The infrastructure-side image (e.g., `infrastructure-side/image/000009.jpg`).
+    pub infrastructure_image_path: String,
+}
+
+/// A single entry of the `data_info.json` of a side (i.e., one frame).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Info {
+    /// The image, relative to the side (e.g., `image/015344.jpg`).
+    pub image_path: String,
+    /// The timestamp of the image (in microseconds).
+    pub image_timestamp: Option<Number>,
+    /// The 2D/3D labels with respect to the camera.
+    pub label_camera_std_path: Option<String>,
+    /// The sequence (i.e., batch) the frame belongs to.
+    pub batch_id: Option<Number>,
+}
+
+/// The identifier of a frame (i.e., the stem of its image).
+pub fn id(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|x| x.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
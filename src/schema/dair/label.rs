@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+use super::info::Number;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Box2d {
+    pub xmin: Number,
+    pub ymin: Number,
+    pub xmax: Number,
+    pub ymax: Number,
+}
+
+/// A single object of a `label/camera` file.
+///
+/// Each object provides both the 2D box on the image and the 3D box with
+/// respect to the camera; only the former is used.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Label {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// 0 (not occluded), 1 (partly occluded), or 2 (mostly occluded)
+    pub occluded_state: Option<Number>,
+    /// 0 (not truncated), 1 (horizontally), or 2 (vertically truncated)
+    pub truncated_state: Option<Number>,
+    #[serde(rename = "2d_box")]
+    pub bbox: Box2d,
+}
+
+impl Label {
+    /// The occlusion state, where a missing state is not occluded.
+    pub fn occlusion(&self) -> f64 {
+        self.occluded_state
+            .as_ref()
+            .map(|x| x.value())
+            .unwrap_or(0.0)
+    }
+
+    /// The truncation state, where a missing state is not truncated.
+    pub fn truncation(&self) -> f64 {
+        self.truncated_state
+            .as_ref()
+            .map(|x| x.value())
+            .unwrap_or(0.0)
+    }
+
+    /// xmin, ymin, xmax, ymax (in pixels)
+    pub fn bbox(&self) -> [f64; 4] {
+        [
+            self.bbox.xmin.value(),
+            self.bbox.ymin.value(),
+            self.bbox.xmax.value(),
+            self.bbox.ymax.value(),
+        ]
+    }
+}
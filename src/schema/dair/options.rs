@@ -0,0 +1,34 @@
+use serde::Deserialize;
+
+use super::label::Label;
+
+/// The set of filters applied to DAIR-V2X labels.
+///
+/// By default, every object is kept.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The maximum occlusion state of an object to keep.
+    pub occlusion: Option<u8>,
+    /// The maximum truncation state of an object to keep.
+    pub truncation: Option<u8>,
+}
+
+impl Options {
+    /// Check if the [`Label`] passes the set of filters.
+    pub fn keep(&self, label: &Label) -> bool {
+        if let Some(occlusion) = self.occlusion {
+            if label.occlusion() > occlusion as f64 {
+                return false;
+            }
+        }
+
+        if let Some(truncation) = self.truncation {
+            if label.truncation() > truncation as f64 {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::label::Label as KittiLabel;
+use self::options::Options as KittiOptions;
+
+pub mod kitti360;
+mod label;
+mod options;
+pub mod tracking;
+
+/// The KITTI 2D object detection benchmark.
+///
+/// The root may either be the `training` directory itself or its parent, in
+/// which case the `ImageSets` directory is searched for next to `training`.
+/// The `test` split is resolved against the `testing` directory, instead, as
+/// its identifiers overlap with those of `training`.
+pub struct Kitti<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Kitti<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", KittiDebug::from(msg));
+        }
+    }
+
+    /// Load the set of splits from the `ImageSets` directory.
+    ///
+    /// Each split is a list of frame identifiers (one per line) and is named
+    /// after its file (e.g., `train`, `val`, `test`). If no `ImageSets`
+    /// directory is found, then every label file forms a single split.
+    fn splits(&self, labels: &Path) -> Result<Vec<(String, Vec<String>)>, Box<dyn Error>> {
+        let dir = dir(&self.root, "ImageSets");
+        let mut splits = Vec::new();
+
+        if dir.is_dir() {
+            let mut paths = fs::read_dir(&dir)?
+                .map(|x| x.map(|x| x.path()))
+                .collect::<Result<Vec<PathBuf>, _>>()?;
+
+            paths.sort();
+
+            for path in paths {
+                if path.extension().map(|x| x != "txt").unwrap_or(true) {
+                    continue;
+                }
+
+                let mut ids = Vec::new();
+
+                for line in BufReader::new(File::open(&path)?).lines() {
+                    let line = line?;
+
+                    if !line.trim().is_empty() {
+                        ids.push(line.trim().to_string());
+                    }
+                }
+
+                let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+                self.debug(&format!("read split `{}` from `{}`", name, path.display()));
+                splits.push((name, ids));
+            }
+        } else {
+            let mut ids = Vec::new();
+
+            for entry in fs::read_dir(labels)? {
+                let path = entry?.path();
+
+                if path.extension().map(|x| x == "txt").unwrap_or(false) {
+                    ids.push(path.file_stem().unwrap().to_string_lossy().into_owned());
+                }
+            }
+
+            ids.sort();
+            splits.push((String::from("training"), ids));
+        }
+
+        Ok(splits)
+    }
+
+    /// Load the set of [`KittiLabel`] of a single frame.
+    fn load(&self, path: &Path) -> Result<Vec<KittiLabel>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(KittiError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut labels = Vec::new();
+
+        for (i, line) in BufReader::new(infile).lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            labels.push(
+                line.parse().map_err(|e| {
+                    KittiError::from(format!("{}:{}: {}", path.display(), i + 1, e))
+                })?,
+            );
+        }
+
+        Ok(labels)
+    }
+}
+
+impl Schema for Kitti<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: KittiOptions = self.config.options("kitti")?;
+
+        let labels = dir(&self.root, "label_2");
+        let images = dir(&self.root, "image_2");
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each split and collect the labels of each
+        // listed frame into a linear stream, in the order of the split.
+        let mut datastreams = Vec::new();
+
+        for (name, ids) in self.splits(&labels)? {
+            // The `test` split does not provide labels.
+            //
+            // Therefore, its frames are read from the `testing` directory and
+            // result in records without annotations.
+            let (images, labels) = if name == "test" {
+                (testing(&self.root, "image_2"), None)
+            } else {
+                (images.clone(), Some(&labels))
+            };
+
+            let mut frames = Vec::new();
+
+            for (index, id) in ids.iter().enumerate() {
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(
+                    String::from("cam::left"),
+                    image(images.join(format!("{}.png", id))),
+                );
+
+                // A missing label file results in a record without annotations
+                // rather than an error.
+                if let Some(path) = labels.map(|x| x.join(format!("{}.txt", id))) {
+                    if path.is_file() {
+                        record.annotations = annotations(&self.load(&path)?, &options);
+                    }
+                }
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+/// Resolve a KITTI directory (e.g., `label_2`).
+///
+/// The directory is first searched for within the root, then within the
+/// `training` directory of the root and, if still not found, next to the root
+/// (i.e., the root is the `training` directory itself).
+fn dir(root: &Path, name: &str) -> PathBuf {
+    let training = root.join("training").join(name);
+
+    let mut candidates = vec![root.join(name), training.clone()];
+    candidates.extend(root.parent().map(|x| x.join(name)));
+
+    candidates
+        .into_iter()
+        .find(|x| x.is_dir())
+        .unwrap_or(training)
+}
+
+/// Resolve a KITTI directory of the `testing` split (e.g., `image_2`).
+///
+/// The `testing` directory is searched for within the root and, if not found,
+/// next to the root (i.e., the root is the `training` directory itself).
+fn testing(root: &Path, name: &str) -> PathBuf {
+    let path = root.join("testing").join(name);
+
+    match root.parent().map(|x| x.join("testing").join(name)) {
+        Some(sibling) if !path.is_dir() && sibling.is_dir() => sibling,
+        _ => path,
+    }
+}
+
+/// Read the [`Image`] along with its dimensions from the file header.
+///
+/// If the image is missing (or unreadable), then no [`Image`] is provided.
+fn image(path: PathBuf) -> Option<Image> {
+    let size = imagesize::size(&path).ok()?;
+    let source = ImageSource::File(path);
+
+    Some(Image::new(source, size.width as u32, size.height as u32))
+}
+
+/// Convert the set of [`KittiLabel`] that pass the filters into annotations.
+///
+/// This is shared between the object and tracking benchmarks.
+fn annotations(labels: &[KittiLabel], options: &KittiOptions) -> HashMap<String, Vec<Annotation>> {
+    let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+    for label in labels.iter().filter(|x| options.keep(x)) {
+        let [left, top, right, bottom] = label.bbox;
+
+        let width = right - left;
+        let height = bottom - top;
+
+        res.entry(label.kind.clone())
+            .or_default()
+            .push(Annotation::new(
+                label.kind.clone(),
+                label.score.unwrap_or(1.0),
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(left + (width / 2.0), top + (height / 2.0)),
+                    width,
+                    height,
+                )),
+            ));
+    }
+
+    res
+}
+
+#[derive(Debug, Clone)]
+struct KittiDebug {
+    msg: String,
+}
+
+impl From<&str> for KittiDebug {
+    fn from(msg: &str) -> Self {
+        KittiDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for KittiDebug {
+    fn from(msg: String) -> Self {
+        KittiDebug { msg }
+    }
+}
+
+impl fmt::Display for KittiDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: kitti: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KittiError {
+    msg: String,
+}
+
+impl From<&str> for KittiError {
+    fn from(msg: &str) -> Self {
+        KittiError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for KittiError {
+    fn from(msg: String) -> Self {
+        KittiError { msg }
+    }
+}
+
+impl fmt::Display for KittiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "kitti: {}", self.msg)
+    }
+}
+
+impl Error for KittiError {}
@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+use crate::schema::Schema;
+
+use super::{KittiDebug, KittiError};
+
+use self::bbox::Object as Kitti360Object;
+use self::calibration::{Perspective, Transform};
+use self::options::Options as Kitti360Options;
+
+mod bbox;
+mod calibration;
+mod options;
+
+type FrameId = usize;
+
+/// The set of perspective cameras (i.e., the directory, the suffix of its
+/// calibration, and its channel).
+const CAMERAS: &[(&str, &str, &str)] = &[
+    ("image_00", "00", "cam::left"),
+    ("image_01", "01", "cam::right"),
+];
+
+/// A perspective camera of the rig.
+struct Kitti360Camera {
+    name: String,
+    channel: String,
+    perspective: Perspective,
+    /// The rectified camera to (GPS/IMU) pose transform.
+    extrinsic: Transform,
+}
+
+/// The KITTI-360 dataset.
+///
+/// Each drive sequence (e.g., `2013_05_28_drive_0000_sync`) forms its own
+/// stream where each frame with a pose forms a [`Frame`] and the 3D boxes of
+/// `data_3d_bboxes` are projected onto the two perspective cameras.
+pub struct Kitti360<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Kitti360<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", KittiDebug::from(msg));
+        }
+    }
+
+    /// Collect the set of sequence box files.
+    fn sequences(&self, options: &Kitti360Options) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let dir = self.root.join("data_3d_bboxes").join(&options.bboxes);
+
+        let mut sequences = Vec::new();
+
+        for entry in fs::read_dir(&dir).or(Err(Box::new(KittiError::from(format!(
+            "unable to open `{}`",
+            dir.display()
+        )))))? {
+            let path = entry?.path();
+
+            if path.extension().map(|x| x == "xml").unwrap_or(false) {
+                sequences.push(path);
+            }
+        }
+
+        sequences.sort();
+
+        Ok(sequences)
+    }
+
+    /// Load the calibration of each perspective camera.
+    fn cameras(&self) -> Result<Vec<Kitti360Camera>, Box<dyn Error>> {
+        let dir = self.root.join("calibration");
+
+        let error = |e: Box<dyn Error>| KittiError::from(format!("{}: {}", dir.display(), e));
+
+        let extrinsics =
+            calibration::extrinsics(&dir.join("calib_cam_to_pose.txt")).map_err(error)?;
+
+        let mut res = Vec::new();
+
+        for (name, suffix, channel) in CAMERAS {
+            let perspective =
+                calibration::perspective(&dir.join("perspective.txt"), suffix).map_err(error)?;
+
+            let extrinsic = extrinsics
+                .get(*name)
+                .ok_or_else(|| error(format!("missing camera `{}`", name).into()))?;
+
+            // The boxes are projected onto the rectified image; therefore, the
+            // rectifying rotation is undone, accordingly.
+            let extrinsic = extrinsic * perspective.rectification.transpose();
+
+            res.push(Kitti360Camera {
+                name: name.to_string(),
+                channel: channel.to_string(),
+                perspective,
+                extrinsic,
+            });
+        }
+
+        Ok(res)
+    }
+
+    /// Project the set of objects of a frame onto a camera.
+    ///
+    /// This includes: (1) moving the objects from the world into the pose of
+    /// the frame, and (2) moving the objects into the camera, accordingly.
+    fn annotations(
+        &self,
+        objects: &[&Kitti360Object],
+        pose: &Transform,
+        camera: &Kitti360Camera,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        let (translation, rotation) = calibration::pose(&(pose * camera.extrinsic));
+        let Perspective {
+            view,
+            width,
+            height,
+            ..
+        } = camera.perspective;
+
+        for object in objects {
+            let a = object.cuboid.clone().into_frame(translation, rotation);
+
+            if a.inside(view, width, height) {
+                res.entry(object.label.clone())
+                    .or_default()
+                    .push(Annotation::new(
+                        object.label.clone(),
+                        1.0,
+                        BoundingBox::AxisAligned(a.region(view)),
+                    ));
+            }
+        }
+
+        res
+    }
+}
+
+impl Schema for Kitti360<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: Kitti360Options = self.config.options("kitti-360")?;
+        let cameras = self.cameras()?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.sequences(&options)? {
+            let sequence = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+            let error = |e: Box<dyn Error>| KittiError::from(format!("{}: {}", sequence, e));
+
+            let objects = fs::read_to_string(&path)
+                .map_err(|e| e.into())
+                .and_then(|x| bbox::parse(&x))
+                .map_err(error)?;
+
+            let poses = calibration::poses(
+                &self
+                    .root
+                    .join("data_poses")
+                    .join(&sequence)
+                    .join("poses.txt"),
+            )
+            .map_err(error)?;
+
+            // Group the objects by frame.
+            //
+            // Only the frames with a pose can be projected onto; therefore,
+            // the objects are only associated with those frames.
+            let mut data: BTreeMap<FrameId, Vec<&Kitti360Object>> = BTreeMap::new();
+
+            for object in objects.iter().filter(|x| options.keep(&x.label)) {
+                for (frame, _) in poses.range(object.frames()) {
+                    data.entry(*frame).or_default().push(object);
+                }
+            }
+
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each frame with a pose and project its
+            // set of objects onto each camera, accordingly.
+            let mut frames = Vec::new();
+
+            for (index, (id, pose)) in poses.iter().enumerate() {
+                let mut frame = Frame::new(index);
+
+                for camera in cameras.iter() {
+                    let path = self
+                        .root
+                        .join("data_2d_raw")
+                        .join(&sequence)
+                        .join(&camera.name)
+                        .join("data_rect")
+                        .join(format!("{:010}.png", id));
+
+                    let image = if path.is_file() {
+                        Some(Image::new(
+                            ImageSource::File(path),
+                            camera.perspective.width as u32,
+                            camera.perspective.height as u32,
+                        ))
+                    } else {
+                        None
+                    };
+
+                    let mut record = DetectionRecord::new(camera.channel.clone(), image);
+
+                    if let Some(objects) = data.get(id) {
+                        record.annotations = self.annotations(objects, pose, camera);
+                    }
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", sequence));
+            datastreams.push((sequence, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
@@ -0,0 +1,147 @@
+use std::error::Error;
+use std::ops::RangeInclusive;
+
+use nalgebra::Vector4;
+use roxmltree::{Document, Node};
+
+use crate::schema::cuboid::Cuboid;
+
+use super::calibration::{self, Transform};
+
+/// A single 3D bounding box of `data_3d_bboxes`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+    pub label: String,
+    /// The frame of a dynamic object or, otherwise, `-1`.
+    pub timestamp: i64,
+    pub start: usize,
+    pub end: usize,
+    /// The box with respect to the world coordinate system.
+    pub cuboid: Cuboid,
+}
+
+/// Find the (trimmed) text of the first direct child element by name.
+fn text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.children()
+        .find(|x| x.is_element() && x.has_tag_name(name))
+        .and_then(|x| x.text())
+        .map(|x| x.trim())
+}
+
+/// Read an OpenCV matrix (i.e., its `<data>`) of the provided shape.
+fn matrix(node: Node, name: &str, rows: usize, cols: usize) -> Result<Vec<f64>, Box<dyn Error>> {
+    let node = node
+        .children()
+        .find(|x| x.is_element() && x.has_tag_name(name))
+        .ok_or_else(|| format!("missing object `{}`", name))?;
+
+    let values = text(node, "data")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|x| x.parse())
+        .collect::<Result<Vec<f64>, _>>()?;
+
+    if values.len() != rows * cols {
+        return Err(format!("expected {}x{} `{}`", rows, cols, name).into());
+    }
+
+    Ok(values)
+}
+
+/// Fit a [`Cuboid`] to the set of (local) vertices and its transform.
+///
+/// The transform scales the box along with its rotation; therefore, the scale
+/// of each axis is moved into the size, accordingly.
+fn cuboid(transform: &Transform, vertices: &[f64]) -> Cuboid {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+
+    for vertex in vertices.chunks(3) {
+        for i in 0..3 {
+            min[i] = min[i].min(vertex[i]);
+            max[i] = max[i].max(vertex[i]);
+        }
+    }
+
+    let mut rotation = Transform::identity();
+    let mut scale = [0.0; 3];
+
+    for (i, scale) in scale.iter_mut().enumerate() {
+        let axis = transform.fixed_view::<3, 1>(0, i);
+        *scale = axis.norm();
+
+        rotation
+            .fixed_view_mut::<3, 1>(0, i)
+            .copy_from(&(axis / *scale));
+    }
+
+    // The center of the box is offset from the origin of the transform if the
+    // vertices are not centered about the origin.
+    let center = transform
+        * Vector4::new(
+            (min[0] + max[0]) / 2.0,
+            (min[1] + max[1]) / 2.0,
+            (min[2] + max[2]) / 2.0,
+            1.0,
+        );
+
+    let (_, quaternion) = calibration::pose(&rotation);
+
+    Cuboid::new(
+        [center.x, center.y, center.z],
+        [
+            (max[1] - min[1]) * scale[1],
+            (max[0] - min[0]) * scale[0],
+            (max[2] - min[2]) * scale[2],
+        ],
+        quaternion,
+    )
+}
+
+impl Object {
+    fn from_node(node: Node) -> Result<Self, Box<dyn Error>> {
+        let label = text(node, "label").ok_or("missing object `label`")?;
+
+        let transform = calibration::transform(&matrix(node, "transform", 4, 4)?)?;
+        let vertices = matrix(node, "vertices", 8, 3)?;
+
+        Ok(Object {
+            label: label.to_string(),
+            timestamp: text(node, "timestamp").unwrap_or("-1").parse()?,
+            start: text(node, "start_frame").unwrap_or("0").parse()?,
+            end: text(node, "end_frame").unwrap_or("0").parse()?,
+            cuboid: cuboid(&transform, &vertices),
+        })
+    }
+
+    /// The set of frames the object is present in.
+    ///
+    /// A dynamic object is annotated once per frame whereas a static object
+    /// is annotated once for its whole span of frames.
+    pub fn frames(&self) -> RangeInclusive<usize> {
+        if self.timestamp >= 0 {
+            self.timestamp as usize..=self.timestamp as usize
+        } else {
+            self.start..=self.end
+        }
+    }
+}
+
+/// Parse the set of objects of a sequence (e.g., `2013_05_28_drive_0000_sync.xml`).
+pub fn parse(s: &str) -> Result<Vec<Object>, Box<dyn Error>> {
+    let document = Document::parse(s)?;
+    let mut objects = Vec::new();
+
+    for node in document
+        .root_element()
+        .children()
+        .filter(|x| x.is_element())
+    {
+        let object =
+            Object::from_node(node).map_err(|e| format!("<{}>: {}", node.tag_name().name(), e))?;
+
+        objects.push(object);
+    }
+
+    Ok(objects)
+}
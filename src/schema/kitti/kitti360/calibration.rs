@@ -0,0 +1,138 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use nalgebra::{Matrix3, SMatrix as StaticMatrix, UnitQuaternion};
+
+/// A rigid transformation as a homogeneous 4x4 matrix.
+pub type Transform = StaticMatrix<f64, 4, 4>;
+
+/// The intrinsics of a (rectified) perspective camera.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Perspective {
+    pub view: StaticMatrix<f64, 3, 3>,
+    /// The rectifying rotation (i.e., `R_rect_0x`).
+    pub rectification: Transform,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A single line of a calibration (or pose) file.
+type Entry = (String, Vec<f64>);
+
+/// Read a set of `key: values` (or `key values`) lines.
+///
+/// The lines with any non-numeric value (e.g., `calib_time`) are skipped.
+fn entries(path: &Path) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let mut res = Vec::new();
+
+    for line in fs::read_to_string(path)?.lines() {
+        let mut fields = line.split_whitespace();
+
+        let key = match fields.next() {
+            Some(key) => key.trim_end_matches(':').to_string(),
+            None => continue,
+        };
+
+        if let Ok(values) = fields.map(|x| x.parse()).collect::<Result<Vec<f64>, _>>() {
+            res.push((key, values));
+        }
+    }
+
+    Ok(res)
+}
+
+/// Construct a [`Transform`] from a row-major 3x4 (or 4x4) matrix.
+pub fn transform(values: &[f64]) -> Result<Transform, Box<dyn Error>> {
+    match values.len() {
+        12 => {
+            let mut m = Transform::identity();
+
+            for (i, x) in values.iter().enumerate() {
+                m[(i / 4, i % 4)] = *x;
+            }
+
+            Ok(m)
+        }
+        16 => Ok(Transform::from_row_slice(values)),
+        n => Err(format!("expected a 3x4 or 4x4 matrix, found {} values", n).into()),
+    }
+}
+
+/// Decompose a [`Transform`] into its translation and rotation (w, x, y, z).
+pub fn pose(m: &Transform) -> ([f64; 3], [f64; 4]) {
+    let rotation: Matrix3<f64> = m.fixed_view::<3, 3>(0, 0).into_owned();
+    let q = UnitQuaternion::from_matrix(&rotation);
+
+    ([m[(0, 3)], m[(1, 3)], m[(2, 3)]], [q.w, q.i, q.j, q.k])
+}
+
+/// Read the rectified intrinsics of a camera (e.g., `00`) from
+/// `calibration/perspective.txt`.
+pub fn perspective(path: &Path, camera: &str) -> Result<Perspective, Box<dyn Error>> {
+    let entries: HashMap<String, Vec<f64>> = entries(path)?.into_iter().collect();
+
+    let get = |key: String| {
+        entries
+            .get(&key)
+            .ok_or_else(|| format!("missing `{}`", key))
+    };
+
+    let projection = get(format!("P_rect_{}", camera))?;
+    let rectification = get(format!("R_rect_{}", camera))?;
+    let size = get(format!("S_rect_{}", camera))?;
+
+    if projection.len() != 12 || rectification.len() != 9 || size.len() != 2 {
+        return Err(format!("malformed calibration of camera `{}`", camera).into());
+    }
+
+    let view = StaticMatrix::<f64, 3, 3>::new(
+        projection[0],
+        projection[1],
+        projection[2],
+        projection[4],
+        projection[5],
+        projection[6],
+        projection[8],
+        projection[9],
+        projection[10],
+    );
+
+    let mut m = Transform::identity();
+    m.fixed_view_mut::<3, 3>(0, 0)
+        .copy_from(&Matrix3::from_row_slice(rectification));
+
+    Ok(Perspective {
+        view,
+        rectification: m,
+        width: size[0],
+        height: size[1],
+    })
+}
+
+/// Read the set of camera to (GPS/IMU) pose transforms from
+/// `calibration/calib_cam_to_pose.txt`, keyed by camera (e.g., `image_00`).
+pub fn extrinsics(path: &Path) -> Result<HashMap<String, Transform>, Box<dyn Error>> {
+    let mut res = HashMap::new();
+
+    for (key, values) in entries(path)? {
+        res.insert(key, transform(&values)?);
+    }
+
+    Ok(res)
+}
+
+/// Read the set of (GPS/IMU) poses from `poses.txt`, keyed by frame.
+///
+/// Each line is the frame followed by a row-major 3x4 matrix. Only a subset of
+/// the frames of a sequence are provided with a pose.
+pub fn poses(path: &Path) -> Result<BTreeMap<usize, Transform>, Box<dyn Error>> {
+    let mut res = BTreeMap::new();
+
+    for (key, values) in entries(path)? {
+        res.insert(key.parse()?, transform(&values)?);
+    }
+
+    Ok(res)
+}
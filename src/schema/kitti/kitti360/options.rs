@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+/// The set of instance classes of KITTI-360.
+const INSTANCES: &[&str] = &[
+    "person",
+    "rider",
+    "car",
+    "truck",
+    "bus",
+    "caravan",
+    "trailer",
+    "train",
+    "motorcycle",
+    "bicycle",
+];
+
+/// The set of options applied to KITTI-360 annotations.
+///
+/// By default, the `train` boxes are imported and only the objects of the
+/// instance classes (e.g., `car`, `person`) are kept as the remaining classes
+/// (e.g., `building`, `pole`) are static scenery.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The set of labels to keep.
+    pub labels: Option<Vec<String>>,
+    /// The directory of boxes within `data_3d_bboxes` (e.g., `train_full`).
+    pub bboxes: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            labels: None,
+            bboxes: String::from("train"),
+        }
+    }
+}
+
+impl Options {
+    /// Check if the label passes the set of filters.
+    pub fn keep(&self, label: &str) -> bool {
+        match &self.labels {
+            Some(labels) => labels.iter().any(|x| x == label),
+            None => INSTANCES.contains(&label),
+        }
+    }
+}
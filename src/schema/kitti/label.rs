@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::str::FromStr;
+
+/// A single object of a KITTI label file.
+///
+/// Only the fields relevant to 2D detection are kept. The remaining 3D
+/// dimensions, location, and rotation are ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub kind: String,
+    /// 0.0 (non-truncated) to 1.0 (truncated), or -1.0 for `DontCare`
+    pub truncation: f64,
+    /// 0 (fully visible), 1 (partly occluded), 2 (largely occluded), or
+    /// 3 (unknown), or -1 for `DontCare`
+    pub occlusion: i8,
+    /// left, top, right, bottom (in pixels)
+    pub bbox: [f64; 4],
+    /// Only present in result files.
+    pub score: Option<f64>,
+}
+
+impl Label {
+    /// Parse the object from its whitespace-separated fields.
+    ///
+    /// This is shared between the object and tracking benchmarks as the
+    /// tracking benchmark simply prefixes each object with the frame and
+    /// track identifiers.
+    pub fn from_fields(fields: &[&str]) -> Result<Self, Box<dyn Error>> {
+        if fields.len() != 15 && fields.len() != 16 {
+            return Err(format!("expected 15 or 16 fields, found {}", fields.len()).into());
+        }
+
+        Ok(Label {
+            kind: fields[0].to_string(),
+            truncation: fields[1].parse()?,
+            occlusion: fields[2].parse()?,
+            bbox: [
+                fields[4].parse()?,
+                fields[5].parse()?,
+                fields[6].parse()?,
+                fields[7].parse()?,
+            ],
+            score: fields.get(15).map(|x| x.parse()).transpose()?,
+        })
+    }
+}
+
+impl FromStr for Label {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Label::from_fields(&s.split_whitespace().collect::<Vec<&str>>())
+    }
+}
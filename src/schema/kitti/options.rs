@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+use super::label::Label;
+
+/// The set of filters applied to KITTI labels.
+///
+/// By default, every object is kept except for `DontCare` regions.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The maximum truncation of an object to keep.
+    pub truncation: Option<f64>,
+    /// The maximum occlusion level of an object to keep.
+    pub occlusion: Option<i8>,
+    /// Keep `DontCare` regions.
+    pub dontcare: bool,
+}
+
+impl Options {
+    /// Check if the [`Label`] passes the set of filters.
+    pub fn keep(&self, label: &Label) -> bool {
+        if label.kind == "DontCare" {
+            return self.dontcare;
+        }
+
+        if let Some(truncation) = self.truncation {
+            if label.truncation > truncation {
+                return false;
+            }
+        }
+
+        if let Some(occlusion) = self.occlusion {
+            if label.occlusion > occlusion {
+                return false;
+            }
+        }
+
+        true
+    }
+}
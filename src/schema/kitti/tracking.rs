@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use strem::datastream::frame::sample::detections::DetectionRecord;
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+use crate::schema::Schema;
+
+use super::label::Label as KittiLabel;
+use super::options::Options as KittiOptions;
+use super::{annotations, dir, image, KittiDebug, KittiError};
+
+type FrameId = usize;
+
+/// The KITTI object tracking benchmark.
+///
+/// Each sequence (e.g., `label_02/0000.txt`) forms its own stream where the
+/// index of each [`Frame`] is the KITTI frame number.
+pub struct KittiTracking<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> KittiTracking<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", KittiDebug::from(msg));
+        }
+    }
+
+    /// Collect the set of sequence identifiers.
+    ///
+    /// The sequences are taken from the label files and, if no labels exist
+    /// (e.g., the `testing` split), from the image directories instead.
+    fn sequences(&self, labels: &Path, images: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut sequences = Vec::new();
+
+        if labels.is_dir() {
+            for entry in fs::read_dir(labels)? {
+                let path = entry?.path();
+
+                if path.extension().map(|x| x == "txt").unwrap_or(false) {
+                    sequences.push(path.file_stem().unwrap().to_string_lossy().into_owned());
+                }
+            }
+        } else {
+            for entry in fs::read_dir(images)? {
+                let path = entry?.path();
+
+                if path.is_dir() {
+                    sequences.push(path.file_name().unwrap().to_string_lossy().into_owned());
+                }
+            }
+        }
+
+        sequences.sort();
+
+        Ok(sequences)
+    }
+
+    /// Load the set of [`KittiLabel`] of a sequence, grouped by frame.
+    ///
+    /// Each line is prefixed by the frame and track identifiers which are
+    /// followed by the same fields as the object benchmark.
+    fn load(&self, path: &Path) -> Result<BTreeMap<FrameId, Vec<KittiLabel>>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(KittiError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut labels: BTreeMap<FrameId, Vec<KittiLabel>> = BTreeMap::new();
+
+        for (i, line) in BufReader::new(infile).lines().enumerate() {
+            let line = line?;
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            if fields.is_empty() {
+                continue;
+            }
+
+            let err = |e: Box<dyn Error>| {
+                KittiError::from(format!("{}:{}: {}", path.display(), i + 1, e))
+            };
+
+            if fields.len() < 2 {
+                return Err(Box::new(err("missing frame and track".into())));
+            }
+
+            let frame: FrameId = fields[0].parse().map_err(|e| err(Box::new(e)))?;
+            let label = KittiLabel::from_fields(&fields[2..]).map_err(err)?;
+
+            labels.entry(frame).or_default().push(label);
+        }
+
+        Ok(labels)
+    }
+
+    /// Count the number of images of a sequence.
+    fn count(&self, images: &Path) -> Result<usize, Box<dyn Error>> {
+        if !images.is_dir() {
+            return Ok(0);
+        }
+
+        let mut count = 0;
+
+        for entry in fs::read_dir(images)? {
+            if entry?
+                .path()
+                .extension()
+                .map(|x| x == "png")
+                .unwrap_or(false)
+            {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+impl Schema for KittiTracking<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: KittiOptions = self.config.options("kitti-tracking")?;
+
+        let labels = dir(&self.root, "label_02");
+        let images = dir(&self.root, "image_02");
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each sequence and create a [`Frame`] for every
+        // frame number, including those without any objects, such that the
+        // timeline of the stream has no gaps.
+        let mut datastreams = Vec::new();
+
+        for sequence in self.sequences(&labels, &images)? {
+            let path = labels.join(format!("{}.txt", sequence));
+
+            let data = if path.is_file() {
+                self.load(&path)?
+            } else {
+                BTreeMap::new()
+            };
+
+            // The length of the sequence is determined by both the labels and
+            // the images as trailing frames may not contain any objects.
+            let directory = images.join(&sequence);
+
+            let length = data
+                .keys()
+                .next_back()
+                .map(|x| x + 1)
+                .unwrap_or(0)
+                .max(self.count(&directory)?);
+
+            let mut frames = Vec::new();
+
+            for index in 0..length {
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(
+                    String::from("cam::left"),
+                    image(directory.join(format!("{:06}.png", index))),
+                );
+
+                if let Some(labels) = data.get(&index) {
+                    record.annotations = annotations(labels, &options);
+                }
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", sequence));
+            datastreams.push((sequence, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
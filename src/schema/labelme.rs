@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::document::{Document as LabelMeDocument, Shape as LabelMeShape};
+
+mod document;
+
+/// Take the run of digits at the start of the characters.
+fn digits(chars: &mut Peekable<Chars>) -> String {
+    let mut res = String::new();
+
+    while let Some(c) = chars.next_if(|x| x.is_ascii_digit()) {
+        res.push(c);
+    }
+
+    res
+}
+
+/// Compare two file names in natural order.
+///
+/// Each run of digits is compared by its value such that `frame_2` precedes
+/// `frame_10`, as expected of numbered video frames.
+fn natural(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, y) = (digits(&mut a), digits(&mut b));
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+
+                match x.len().cmp(&y.len()).then_with(|| x.cmp(y)) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+            }
+            (Some(x), Some(y)) => {
+                match x.cmp(&y) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+/// The LabelMe per-image JSON annotations.
+///
+/// The input may either be a single annotation or a directory of annotations
+/// where each (sub)directory forms its own stream, ordered by the natural
+/// order of the file names.
+pub struct LabelMe<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> LabelMe<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", LabelMeDebug::from(msg));
+        }
+    }
+
+    /// Recursively collect the annotations under the directory.
+    ///
+    /// The annotations are grouped by the directory (relative to `base`) in
+    /// which they reside such that each subdirectory forms its own stream.
+    fn files(
+        &self,
+        base: &Path,
+        dir: &Path,
+        res: &mut BTreeMap<PathBuf, Vec<PathBuf>>,
+    ) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                self.files(base, &path, res)?;
+            } else if path.extension().map(|x| x == "json").unwrap_or(false) {
+                let group = dir.strip_prefix(base)?.to_path_buf();
+                res.entry(group).or_default().push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Name the stream after the subdirectory.
+    ///
+    /// Nested directories are joined with a `-` so that the name remains a
+    /// valid file name when exported.
+    fn name(&self, group: &Path) -> String {
+        let name = group
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<String>>()
+            .join("-");
+
+        if name.is_empty() {
+            self.path
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("labelme"))
+        } else {
+            name
+        }
+    }
+
+    /// Load the [`LabelMeDocument`] of a single image.
+    fn load(&self, path: &Path) -> Result<LabelMeDocument, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(LabelMeError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+
+        Ok(serde_json::from_reader(reader)
+            .map_err(|e| LabelMeError::from(format!("{}: {}", path.display(), e)))?)
+    }
+
+    /// Reference the image of the annotation.
+    ///
+    /// The `imagePath` is relative to the annotation itself.
+    fn image(&self, path: &Path, document: &LabelMeDocument) -> Image {
+        let path = path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&document.image_path);
+
+        Image::new(
+            ImageSource::File(path),
+            document.image_width,
+            document.image_height,
+        )
+    }
+
+    fn annotations(&self, shapes: &[LabelMeShape]) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for shape in shapes.iter() {
+            let [xmin, ymin, xmax, ymax] = match shape.bbox() {
+                Some(bbox) => bbox,
+                None => continue,
+            };
+
+            let width = xmax - xmin;
+            let height = ymax - ymin;
+
+            res.entry(shape.label.clone())
+                .or_default()
+                .push(Annotation::new(
+                    shape.label.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(aa::Region::new(
+                        Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
+                        width,
+                        height,
+                    )),
+                ));
+        }
+
+        res
+    }
+}
+
+impl Schema for LabelMe<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let mut groups = BTreeMap::new();
+
+        if self.path.is_dir() {
+            self.files(&self.path, &self.path, &mut groups)?;
+        } else {
+            groups.insert(PathBuf::new(), vec![self.path.clone()]);
+        }
+
+        // Construct the set of [`Frame`].
+        //
+        // The annotations of each group are ordered by the natural order of
+        // their file names to form a linear stream.
+        let mut datastreams = Vec::new();
+
+        for (group, mut files) in groups {
+            files.sort_by(|a, b| {
+                natural(
+                    &a.file_name().unwrap().to_string_lossy(),
+                    &b.file_name().unwrap().to_string_lossy(),
+                )
+            });
+
+            let mut frames = Vec::new();
+
+            for (index, path) in files.iter().enumerate() {
+                let document = self.load(path)?;
+
+                let mut frame = Frame::new(index);
+                let mut record =
+                    DetectionRecord::new(String::from("cam"), Some(self.image(path, &document)));
+
+                record.annotations = self.annotations(&document.shapes);
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            let name = self.name(&group);
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LabelMeDebug {
+    msg: String,
+}
+
+impl From<&str> for LabelMeDebug {
+    fn from(msg: &str) -> Self {
+        LabelMeDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for LabelMeDebug {
+    fn from(msg: String) -> Self {
+        LabelMeDebug { msg }
+    }
+}
+
+impl fmt::Display for LabelMeDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: labelme: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LabelMeError {
+    msg: String,
+}
+
+impl From<&str> for LabelMeError {
+    fn from(msg: &str) -> Self {
+        LabelMeError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for LabelMeError {
+    fn from(msg: String) -> Self {
+        LabelMeError { msg }
+    }
+}
+
+impl fmt::Display for LabelMeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "labelme: {}", self.msg)
+    }
+}
+
+impl Error for LabelMeError {}
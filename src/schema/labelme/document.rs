@@ -0,0 +1,66 @@
+use serde::Deserialize;
+
+/// A single shape of a LabelMe annotation.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Shape {
+    pub label: String,
+    /// The set of (x, y) points (in pixels).
+    pub points: Vec<[f64; 2]>,
+    /// The older releases of LabelMe only provide polygons and, as such, do
+    /// not set the type.
+    #[serde(default = "Shape::polygon")]
+    pub shape_type: String,
+}
+
+impl Shape {
+    fn polygon() -> String {
+        String::from("polygon")
+    }
+
+    /// The axis-aligned box about the shape, if supported.
+    ///
+    /// A `rectangle` is given by two opposite corners (in any order) whereas a
+    /// `polygon` is given by each of its vertices; therefore, both reduce to
+    /// the extent of their points. The remaining types (e.g., `circle`,
+    /// `line`) are not supported.
+    ///
+    /// xmin, ymin, xmax, ymax (in pixels)
+    pub fn bbox(&self) -> Option<[f64; 4]> {
+        let n = match &self.shape_type[..] {
+            "rectangle" => 2,
+            "polygon" => 3,
+            _ => return None,
+        };
+
+        if self.points.len() < n {
+            return None;
+        }
+
+        let mut bbox = [
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ];
+
+        for [x, y] in self.points.iter() {
+            bbox[0] = bbox[0].min(*x);
+            bbox[1] = bbox[1].min(*y);
+            bbox[2] = bbox[2].max(*x);
+            bbox[3] = bbox[3].max(*y);
+        }
+
+        Some(bbox)
+    }
+}
+
+/// A LabelMe annotation file (i.e., one image).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    pub shapes: Vec<Shape>,
+    /// The path to the image (relative to the annotation).
+    pub image_path: String,
+    pub image_width: u32,
+    pub image_height: u32,
+}
@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::options::Options as LsOptions;
+use self::task::{Completion as LsCompletion, Region as LsRegion, Task as LsTask};
+use self::value::{Rectangle as LsRectangle, VideoRectangle as LsVideoRectangle};
+
+mod options;
+mod task;
+mod value;
+mod video;
+
+/// The Label Studio JSON export.
+///
+/// The input may either be a single export or a directory of exports. The
+/// image tasks of an export form a single stream (ordered by task) whereas
+/// each video task forms its own stream with every frame of the video.
+pub struct LabelStudio<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> LabelStudio<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", LabelStudioDebug::from(msg));
+        }
+    }
+
+    /// Load the set of [`LsTask`] from an export.
+    fn load(&self, path: &Path) -> Result<Vec<LsTask>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(LabelStudioError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+        let data = serde_json::from_reader(reader)?;
+
+        self.debug(&format!(
+            "serde: deserialized data from `{}`",
+            path.display()
+        ));
+
+        Ok(data)
+    }
+
+    /// The set of exports to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "json").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Select the completion of a task to import.
+    ///
+    /// The first completion that was not cancelled is used.
+    fn completion<'b>(&self, task: &'b LsTask, options: &LsOptions) -> Option<&'b LsCompletion> {
+        let completions = if options.predictions {
+            &task.predictions
+        } else {
+            &task.annotations
+        };
+
+        completions.iter().find(|x| !x.was_cancelled)
+    }
+
+    /// Resolve the source file of a task.
+    ///
+    /// The files served by Label Studio from local storage are referenced as
+    /// `/data/local-files/?d=<path>`; therefore, the path is extracted.
+    fn source(&self, task: &LsTask, key: &str) -> Option<PathBuf> {
+        let value = task
+            .data
+            .get(key)
+            .or_else(|| task.data.values().find(|x| x.is_string()))?
+            .as_str()?;
+
+        let value = value.split_once("?d=").map(|x| x.1).unwrap_or(value);
+
+        Some(PathBuf::from(value))
+    }
+
+    /// Read the dimensions (width, height) of the source file of a task.
+    ///
+    /// If the source is missing (or unreadable), then no dimensions are
+    /// provided.
+    fn size(&self, task: &LsTask, key: &str) -> Option<(u32, u32)> {
+        let path = self.source(task, key)?;
+
+        if key == "video" {
+            let mut infile = File::open(&path).ok()?;
+            return video::size(&mut infile).ok().flatten();
+        }
+
+        let size = imagesize::size(&path).ok()?;
+        Some((size.width as u32, size.height as u32))
+    }
+
+    /// The original dimensions (width, height) of a result.
+    ///
+    /// If the result does not provide its dimensions, then the dimensions of
+    /// the source file are used or, otherwise, the `dimensions` option.
+    fn dimensions(
+        &self,
+        region: &LsRegion,
+        size: Option<(u32, u32)>,
+        options: &LsOptions,
+    ) -> Result<(u32, u32), Box<dyn Error>> {
+        match (region.original_width, region.original_height) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => Ok(size.or(options.dimensions).ok_or_else(|| {
+                LabelStudioError::from("missing original dimensions (see the `dimensions` option)")
+            })?),
+        }
+    }
+
+    /// Convert a percent-based box into an [`Annotation`].
+    ///
+    /// A rotated box is annotated with its axis-aligned extent, where the box
+    /// is rotated (clockwise) about its top-left corner.
+    fn annotation(
+        &self,
+        label: &str,
+        score: f64,
+        [x, y, width, height]: [f64; 4],
+        rotation: f64,
+        (w, h): (u32, u32),
+    ) -> Annotation {
+        let [x, y] = [x / 100.0 * w as f64, y / 100.0 * h as f64];
+        let [width, height] = [width / 100.0 * w as f64, height / 100.0 * h as f64];
+
+        let (sin, cos) = rotation.to_radians().sin_cos();
+
+        let corners = [
+            (0.0, 0.0),
+            (width * cos, width * sin),
+            (-height * sin, height * cos),
+            (width * cos - height * sin, width * sin + height * cos),
+        ];
+
+        let [mut left, mut top] = [f64::INFINITY; 2];
+        let [mut right, mut bottom] = [f64::NEG_INFINITY; 2];
+
+        for (dx, dy) in corners {
+            left = left.min(x + dx);
+            top = top.min(y + dy);
+            right = right.max(x + dx);
+            bottom = bottom.max(y + dy);
+        }
+
+        let width = right - left;
+        let height = bottom - top;
+
+        Annotation::new(
+            label.to_string(),
+            score,
+            BoundingBox::AxisAligned(aa::Region::new(
+                Point::new(left + (width / 2.0), top + (height / 2.0)),
+                width,
+                height,
+            )),
+        )
+    }
+
+    /// Construct the [`Frame`] of an image task.
+    fn image(
+        &self,
+        task: &LsTask,
+        index: usize,
+        options: &LsOptions,
+    ) -> Result<Frame, Box<dyn Error>> {
+        let mut frame = Frame::new(index);
+        let mut annotations: HashMap<String, Vec<Annotation>> = HashMap::new();
+        let mut dimensions = None;
+
+        let size = self.size(task, "image");
+
+        if let Some(completion) = self.completion(task, options) {
+            for region in completion.result.iter() {
+                if region.kind != "rectanglelabels" {
+                    continue;
+                }
+
+                let value: LsRectangle = serde_json::from_value(region.value.clone())?;
+                let size = self.dimensions(region, size, options)?;
+                let score = region.score.or(completion.score).unwrap_or(1.0);
+
+                for label in value.rectanglelabels.iter() {
+                    annotations
+                        .entry(label.clone())
+                        .or_default()
+                        .push(self.annotation(
+                            label,
+                            score,
+                            [value.x, value.y, value.width, value.height],
+                            value.rotation,
+                            size,
+                        ));
+                }
+
+                dimensions = Some(size);
+            }
+        }
+
+        // The dimensions are only provided as part of a result.
+        //
+        // Therefore, for a task without results, the dimensions are read from
+        // the image itself (if available).
+        let image = self.source(task, "image").and_then(|path| {
+            let (width, height) = dimensions.or(size)?;
+            Some(Image::new(ImageSource::File(path), width, height))
+        });
+
+        let mut record = DetectionRecord::new(String::from("cam"), image);
+        record.annotations = annotations;
+
+        // INSERT
+        frame.samples.push(Sample::ObjectDetection(record));
+
+        Ok(frame)
+    }
+
+    /// Construct the set of [`Frame`] of a video task.
+    ///
+    /// The keyframes of each result are expanded such that every frame of the
+    /// video is present, accordingly.
+    fn video(&self, task: &LsTask, options: &LsOptions) -> Result<Vec<Frame>, Box<dyn Error>> {
+        let mut results = Vec::new();
+
+        // The original dimensions are usually not provided for a video.
+        //
+        // Therefore, the dimensions are read from the video itself, if
+        // needed and available.
+        let mut size = None;
+
+        if let Some(completion) = self.completion(task, options) {
+            for region in completion.result.iter() {
+                if region.kind != "videorectangle" {
+                    continue;
+                }
+
+                let value: LsVideoRectangle = serde_json::from_value(region.value.clone())?;
+                let score = region.score.or(completion.score).unwrap_or(1.0);
+
+                if size.is_none() && region.original_width.is_none() {
+                    size = self.size(task, "video");
+                }
+
+                results.push((value, self.dimensions(region, size, options)?, score));
+            }
+        }
+
+        // Determine the number of frames of the video.
+        //
+        // If the number of frames is not provided, then the last keyframe of
+        // any result is used, instead.
+        let last = results
+            .iter()
+            .map(|(value, _, _)| {
+                value
+                    .frames_count
+                    .or_else(|| value.sequence.iter().map(|x| x.frame).max())
+                    .unwrap_or(0)
+            })
+            .max()
+            .unwrap_or(0);
+
+        let mut annotations: Vec<HashMap<String, Vec<Annotation>>> =
+            (0..last).map(|_| HashMap::new()).collect();
+
+        for (value, size, score) in results.iter() {
+            for keyframe in value.expand(last) {
+                if keyframe.frame == 0 || keyframe.frame > last {
+                    continue;
+                }
+
+                for label in value.labels.iter() {
+                    annotations[keyframe.frame - 1]
+                        .entry(label.clone())
+                        .or_default()
+                        .push(self.annotation(
+                            label,
+                            *score,
+                            [keyframe.x, keyframe.y, keyframe.width, keyframe.height],
+                            keyframe.rotation,
+                            *size,
+                        ));
+                }
+            }
+        }
+
+        let mut frames = Vec::new();
+
+        for (index, annotations) in annotations.into_iter().enumerate() {
+            let mut frame = Frame::new(index);
+            let mut record = DetectionRecord::new(String::from("cam"), None);
+            record.annotations = annotations;
+
+            // INSERT
+            frame.samples.push(Sample::ObjectDetection(record));
+            frames.push(frame);
+        }
+
+        Ok(frames)
+    }
+}
+
+impl Schema for LabelStudio<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: LsOptions = self.config.options("labelstudio")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+            let mut tasks = self.load(&path)?;
+            tasks.sort_by_key(|x| x.id);
+
+            // Construct the set of [`Frame`].
+            //
+            // A task is considered a video task if its source is a video or
+            // any of its results is a `videorectangle`.
+            let mut frames = Vec::new();
+
+            for task in tasks.iter() {
+                let video = task.data.contains_key("video")
+                    || self
+                        .completion(task, &options)
+                        .is_some_and(|x| x.result.iter().any(|x| x.kind == "videorectangle"));
+
+                if video {
+                    let name = format!("{}-{}", name, task.id);
+
+                    self.debug(&format!("imported stream `{}`", name));
+                    datastreams.push((name, self.video(task, &options)?));
+                } else {
+                    frames.push(self.image(task, frames.len(), &options)?);
+                }
+            }
+
+            if !frames.is_empty() {
+                self.debug(&format!("imported stream `{}`", name));
+                datastreams.push((name, frames));
+            }
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LabelStudioDebug {
+    msg: String,
+}
+
+impl From<&str> for LabelStudioDebug {
+    fn from(msg: &str) -> Self {
+        LabelStudioDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for LabelStudioDebug {
+    fn from(msg: String) -> Self {
+        LabelStudioDebug { msg }
+    }
+}
+
+impl fmt::Display for LabelStudioDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: labelstudio: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LabelStudioError {
+    msg: String,
+}
+
+impl From<&str> for LabelStudioError {
+    fn from(msg: &str) -> Self {
+        LabelStudioError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for LabelStudioError {
+    fn from(msg: String) -> Self {
+        LabelStudioError { msg }
+    }
+}
+
+impl fmt::Display for LabelStudioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "labelstudio: {}", self.msg)
+    }
+}
+
+impl Error for LabelStudioError {}
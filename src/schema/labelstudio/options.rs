@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+/// The set of options applied to Label Studio exports.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// Import the predictions rather than the annotations of each task.
+    pub predictions: bool,
+    /// The dimensions (width, height) of the videos.
+    ///
+    /// This is only used when a result does not provide its original
+    /// dimensions, as is the case for some video exports, and the dimensions
+    /// can not be read from the source file itself.
+    pub dimensions: Option<(u32, u32)>,
+}
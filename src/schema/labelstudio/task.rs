@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A single result (i.e., region) of an annotation or prediction.
+///
+/// The `value` depends on the `type` of the result and is interpreted by the
+/// importer, accordingly.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Region {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub original_width: Option<u32>,
+    pub original_height: Option<u32>,
+    pub value: Value,
+    /// Only present in predictions.
+    pub score: Option<f64>,
+}
+
+/// An annotation (or prediction) of a task.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Completion {
+    #[serde(default)]
+    pub result: Vec<Region>,
+    #[serde(default)]
+    pub was_cancelled: bool,
+    /// Only present in predictions.
+    pub score: Option<f64>,
+}
+
+/// A single task of a Label Studio JSON export.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Task {
+    pub id: u64,
+    /// image, video (or any other key of the labeling config)
+    pub data: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub annotations: Vec<Completion>,
+    #[serde(default)]
+    pub predictions: Vec<Completion>,
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+
+/// The value of a `rectanglelabels` result.
+///
+/// The coordinates are percentages (0 to 100) of the original dimensions.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Rectangle {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// The clockwise rotation (in degrees) about the top-left corner.
+    #[serde(default)]
+    pub rotation: f64,
+    #[serde(default)]
+    pub rectanglelabels: Vec<String>,
+}
+
+/// A single keyframe of a `videorectangle` result.
+///
+/// The frames are numbered from one and the coordinates are percentages (0 to
+/// 100) of the original dimensions.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Keyframe {
+    pub frame: usize,
+    pub enabled: bool,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// The clockwise rotation (in degrees) about the top-left corner.
+    #[serde(default)]
+    pub rotation: f64,
+}
+
+/// The value of a `videorectangle` result.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoRectangle {
+    pub frames_count: Option<usize>,
+    #[serde(default)]
+    pub sequence: Vec<Keyframe>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl Keyframe {
+    /// Linearly interpolate between two keyframes at a frame.
+    fn interpolate(&self, other: &Keyframe, frame: usize) -> Keyframe {
+        let t = (frame - self.frame) as f64 / (other.frame - self.frame) as f64;
+
+        Keyframe {
+            frame,
+            enabled: true,
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            width: self.width + (other.width - self.width) * t,
+            height: self.height + (other.height - self.height) * t,
+            rotation: self.rotation + (other.rotation - self.rotation) * t,
+        }
+    }
+}
+
+impl VideoRectangle {
+    /// Expand the keyframes into a box per frame (up to and including `last`).
+    ///
+    /// This follows the semantics of Label Studio where (1) an enabled
+    /// keyframe is interpolated towards the next keyframe (or persists to the
+    /// end), and (2) a disabled keyframe ends the region after its frame until
+    /// the next keyframe, accordingly.
+    pub fn expand(&self, last: usize) -> Vec<Keyframe> {
+        let mut sequence = self.sequence.clone();
+        sequence.sort_by_key(|x| x.frame);
+
+        let mut res = Vec::new();
+
+        for (i, keyframe) in sequence.iter().enumerate() {
+            let next = sequence.get(i + 1);
+
+            if !keyframe.enabled {
+                res.push(keyframe.clone());
+                continue;
+            }
+
+            let end = next.map(|x| x.frame).unwrap_or(last + 1).min(last + 1);
+
+            for frame in keyframe.frame..end {
+                res.push(match next {
+                    Some(next) if frame != keyframe.frame => keyframe.interpolate(next, frame),
+                    _ => Keyframe {
+                        frame,
+                        ..keyframe.clone()
+                    },
+                });
+            }
+        }
+
+        res
+    }
+}
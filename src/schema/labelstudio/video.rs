@@ -0,0 +1,136 @@
+use std::error::Error;
+use std::io::{Read, Seek, SeekFrom};
+
+/// The set of boxes that contain the track header (i.e., `moov/trak/tkhd`).
+const CONTAINERS: &[&[u8; 4]] = &[b"moov", b"trak"];
+
+/// Read the dimensions (width, height) of an MP4 (or QuickTime) video.
+///
+/// The dimensions are taken from the track header of the first track with a
+/// non-zero size (i.e., the video track). The boxes are walked by seeking
+/// past their contents such that the media data is never read.
+pub fn size<R: Read + Seek>(reader: &mut R) -> Result<Option<(u32, u32)>, Box<dyn Error>> {
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    walk(reader, end)
+}
+
+/// Walk the boxes from the current position up to `end`.
+fn walk<R: Read + Seek>(reader: &mut R, end: u64) -> Result<Option<(u32, u32)>, Box<dyn Error>> {
+    let mut pos = reader.stream_position()?;
+
+    while pos + 8 <= end {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+
+        let mut size = u32::from_be_bytes(header[..4].try_into()?) as u64;
+        let kind: [u8; 4] = header[4..].try_into()?;
+        let mut offset = 8;
+
+        // A size of one is followed by the (64-bit) size whereas a size of
+        // zero extends the box to the end of its parent.
+        if size == 1 {
+            let mut buf = [0u8; 8];
+            reader.read_exact(&mut buf)?;
+
+            size = u64::from_be_bytes(buf);
+            offset = 16;
+        } else if size == 0 {
+            size = end - pos;
+        }
+
+        if size < offset || pos + size > end {
+            return Err("malformed video box".into());
+        }
+
+        if CONTAINERS.contains(&&kind) {
+            if let Some(res) = walk(reader, pos + size)? {
+                return Ok(Some(res));
+            }
+        } else if &kind == b"tkhd" {
+            if let Some(res) = tkhd(reader, size - offset)? {
+                return Ok(Some(res));
+            }
+        }
+
+        pos += size;
+        reader.seek(SeekFrom::Start(pos))?;
+    }
+
+    Ok(None)
+}
+
+/// Read the dimensions of a track header, if not zero.
+///
+/// The width and height are the last two fields of the header, stored as
+/// 16.16 fixed-point numbers.
+fn tkhd<R: Read>(reader: &mut R, size: u64) -> Result<Option<(u32, u32)>, Box<dyn Error>> {
+    if !(8..=1024).contains(&size) {
+        return Err("malformed track header".into());
+    }
+
+    let mut buf = vec![0u8; size as usize];
+    reader.read_exact(&mut buf)?;
+
+    let n = buf.len();
+    let width = u32::from_be_bytes(buf[n - 8..n - 4].try_into()?) >> 16;
+    let height = u32::from_be_bytes(buf[n - 4..].try_into()?) >> 16;
+
+    if width == 0 || height == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some((width, height)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn mp4box(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut buf = ((data.len() + 8) as u32).to_be_bytes().to_vec();
+        buf.extend(kind);
+        buf.extend(data);
+
+        buf
+    }
+
+    fn tkhd(width: u32, height: u32) -> Vec<u8> {
+        let mut buf = vec![0u8; 76];
+        buf.extend((width << 16).to_be_bytes());
+        buf.extend((height << 16).to_be_bytes());
+
+        mp4box(b"tkhd", &buf)
+    }
+
+    #[test]
+    fn video() {
+        let audio = mp4box(b"trak", &tkhd(0, 0));
+        let video = mp4box(b"trak", &tkhd(1920, 1080));
+
+        let buf = [
+            mp4box(b"ftyp", b"isom"),
+            mp4box(b"mdat", &[0; 32]),
+            mp4box(b"moov", &[audio, video].concat()),
+        ]
+        .concat();
+
+        assert_eq!(size(&mut Cursor::new(buf)).unwrap(), Some((1920, 1080)));
+    }
+
+    #[test]
+    fn malformed() {
+        let mut buf = mp4box(b"moov", &tkhd(1920, 1080));
+        buf[3] = 0xff;
+
+        assert!(size(&mut Cursor::new(buf)).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(size(&mut Cursor::new(Vec::new())).unwrap(), None);
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::ros::decode::Decoder as RosDecoder;
+use super::ros::definition::{normalize, Registry as RosRegistry};
+use super::ros::detection::Detections as RosDetections;
+use super::ros::frame;
+use super::ros::options::Options as McapOptions;
+use super::ros::value::Value as RosValue;
+use super::Schema;
+
+use self::reader::{Channel as McapChannel, McapReader, Schema as McapSchema};
+
+mod reader;
+
+type Topic = String;
+type Timestamp = i64;
+
+/// The set of message types supported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    /// A `vision_msgs/msg/Detection2DArray`.
+    Detections,
+    /// A `foxglove_msgs/msg/ImageAnnotations` (or `foxglove.ImageAnnotations`).
+    Annotations,
+}
+
+impl Kind {
+    fn parse(name: &str) -> Option<Self> {
+        match &normalize(name)[..] {
+            "vision_msgs/Detection2DArray" => Some(Kind::Detections),
+            "foxglove_msgs/ImageAnnotations" | "foxglove.ImageAnnotations" => {
+                Some(Kind::Annotations)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The decoder of the messages of a channel.
+enum Decoder {
+    /// The CDR-encoded messages (with a `ros2msg` schema).
+    Cdr(RosRegistry),
+    Json,
+}
+
+/// The MCAP (e.g., ROS2 and Foxglove) format.
+///
+/// The input may either be a single MCAP file or a directory of MCAP files
+/// where each file forms a stream. Each `vision_msgs/msg/Detection2DArray` and
+/// `foxglove.ImageAnnotations` topic maps to a channel and the messages are
+/// grouped into frames by their timestamps.
+pub struct Mcap<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Mcap<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", McapDebug::from(msg));
+        }
+    }
+
+    /// The set of MCAP files to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "mcap").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Select the decoder of a channel.
+    ///
+    /// Only the CDR (with a `ros2msg` schema) and JSON encodings are
+    /// supported; therefore, any other encoding (e.g., the `protobuf`
+    /// encoding of `foxglove.ImageAnnotations`) results in an error.
+    fn decoder(channel: &McapChannel, schema: &McapSchema) -> Result<Decoder, String> {
+        match (&channel.encoding[..], &schema.encoding[..]) {
+            ("cdr", "ros2msg") => {
+                let definition = String::from_utf8_lossy(&schema.data);
+                let registry = RosRegistry::parse(&schema.name, &definition)?;
+
+                Ok(Decoder::Cdr(registry))
+            }
+            ("json", _) => Ok(Decoder::Json),
+            (encoding, "") => Err(format!("unsupported message encoding `{}`", encoding)),
+            (encoding, x) => Err(format!(
+                "unsupported message encoding `{}` (with `{}` schema)",
+                encoding, x
+            )),
+        }
+    }
+
+    /// Load the set of detections of an MCAP file.
+    ///
+    /// The header (or annotation) timestamp of each message is used, if set;
+    /// otherwise, the time the message was recorded is used, instead.
+    fn load(
+        &self,
+        path: &Path,
+        options: &McapOptions,
+    ) -> Result<Vec<(Timestamp, Topic, RosDetections)>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(McapError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut reader = McapReader::new(BufReader::new(infile))
+            .map_err(|e| McapError::from(format!("{}: {}", path.display(), e)))?;
+
+        let kind = |channel: &McapChannel, schema: &McapSchema| {
+            Kind::parse(&schema.name).filter(|_| options.keep(&channel.topic))
+        };
+
+        let messages = reader
+            .messages(|channel, schema| kind(channel, schema).is_some())
+            .map_err(|e| McapError::from(format!("{}: {}", path.display(), e)))?;
+
+        // Set up internal database.
+        //
+        // The set of message definitions is parsed once per channel. As an
+        // unindexed file only provides its channels as they are found, the
+        // channels are resolved once the messages are read.
+        let mut channels = HashMap::new();
+
+        for channel in reader.channels.values() {
+            let schema = match reader.schemas.get(&channel.schema) {
+                Some(schema) => schema,
+                None => continue,
+            };
+
+            let kind = match kind(channel, schema) {
+                Some(kind) => kind,
+                None => continue,
+            };
+
+            let decoder = Self::decoder(channel, schema).map_err(|e| {
+                McapError::from(format!("{}: {}: {}", path.display(), channel.topic, e))
+            })?;
+
+            self.debug(&format!(
+                "found topic `{}` ({}, {})",
+                channel.topic, schema.name, channel.encoding
+            ));
+
+            channels.insert(channel.id, (channel.topic.clone(), kind, decoder));
+        }
+
+        let mut res = Vec::new();
+
+        for message in messages {
+            // The messages of a channel without a schema are skipped.
+            let (topic, kind, decoder) = match channels.get(&message.channel) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let value = match decoder {
+                Decoder::Cdr(registry) => {
+                    RosDecoder::cdr(registry, &message.data).and_then(|x| x.decode())
+                }
+                Decoder::Json => serde_json::from_slice::<serde_json::Value>(&message.data)
+                    .map(|x| RosValue::from(&x))
+                    .map_err(|e| e.to_string()),
+            };
+
+            let detections = value
+                .and_then(|x| match kind {
+                    Kind::Detections => RosDetections::from_value(&x),
+                    Kind::Annotations => RosDetections::from_annotations(&x),
+                })
+                .map_err(|e| McapError::from(format!("{}: {}", topic, e)))?;
+
+            res.push((
+                detections.stamp.unwrap_or(message.time),
+                topic.clone(),
+                detections,
+            ));
+        }
+
+        self.debug(&format!(
+            "decoded {} messages from `{}`",
+            res.len(),
+            path.display()
+        ));
+
+        Ok(res)
+    }
+}
+
+impl Schema for Mcap<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: McapOptions = self.config.options("mcap")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let detections = self.load(&path, &options)?;
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frame::frames(detections, &options)));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct McapDebug {
+    msg: String,
+}
+
+impl From<&str> for McapDebug {
+    fn from(msg: &str) -> Self {
+        McapDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for McapDebug {
+    fn from(msg: String) -> Self {
+        McapDebug { msg }
+    }
+}
+
+impl fmt::Display for McapDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: mcap: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct McapError {
+    msg: String,
+}
+
+impl From<&str> for McapError {
+    fn from(msg: &str) -> Self {
+        McapError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for McapError {
+    fn from(msg: String) -> Self {
+        McapError { msg }
+    }
+}
+
+impl fmt::Display for McapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mcap: {}", self.msg)
+    }
+}
+
+impl Error for McapError {}
@@ -0,0 +1,504 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+/// The magic bytes at the start and end of the file.
+const MAGIC: &[u8] = b"\x89MCAP0\r\n";
+
+/// The set of record types (i.e., `op` codes).
+const OP_HEADER: u8 = 0x01;
+const OP_FOOTER: u8 = 0x02;
+const OP_SCHEMA: u8 = 0x03;
+const OP_CHANNEL: u8 = 0x04;
+const OP_MESSAGE: u8 = 0x05;
+const OP_CHUNK: u8 = 0x06;
+const OP_CHUNK_INDEX: u8 = 0x08;
+const OP_DATA_END: u8 = 0x0F;
+
+/// The length of the footer record (i.e., its op code, length, and fields).
+const FOOTER_LENGTH: u64 = 1 + 8 + 8 + 8 + 4;
+
+/// A schema (i.e., the message definition of a channel).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    pub id: u16,
+    /// The message type (e.g., `vision_msgs/msg/Detection2DArray`).
+    pub name: String,
+    /// The encoding of the definition (e.g., `ros2msg` or `jsonschema`).
+    pub encoding: String,
+    pub data: Vec<u8>,
+}
+
+/// A channel (i.e., a topic and its schema).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Channel {
+    pub id: u16,
+    pub schema: u16,
+    pub topic: String,
+    /// The encoding of the messages (e.g., `cdr` or `json`).
+    pub encoding: String,
+}
+
+/// A serialized message of a channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub channel: u16,
+    /// The time the message was recorded (in nanoseconds).
+    pub time: i64,
+    pub data: Vec<u8>,
+}
+
+/// A single record (i.e., its op code and data).
+type Record = (u8, Vec<u8>);
+
+/// The location of a chunk, as provided by the summary.
+struct ChunkIndex {
+    offset: u64,
+    /// The set of channels with at least one message in the chunk.
+    channels: BTreeSet<u16>,
+}
+
+/// A cursor over the fields of a record.
+///
+/// Each value is stored in little-endian. The strings and byte arrays are
+/// prefixed by their length as a `u32` and the maps by their length (in bytes)
+/// as a `u32`.
+struct Fields<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        if n > self.buf.len() {
+            return Err("truncated record".into());
+        }
+
+        let (bytes, rest) = self.buf.split_at(n);
+        self.buf = rest;
+
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], Box<dyn Error>> {
+        let n = self.u32()? as usize;
+        self.take(n)
+    }
+
+    fn string(&mut self) -> Result<String, Box<dyn Error>> {
+        Ok(String::from_utf8_lossy(self.bytes()?).into_owned())
+    }
+}
+
+/// Read the next record, if any.
+fn record<R: Read>(reader: &mut R) -> Result<Option<Record>, Box<dyn Error>> {
+    let mut op = [0u8; 1];
+
+    match reader.read_exact(&mut op) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(Box::new(e)),
+    }
+
+    let mut length = [0u8; 8];
+    reader.read_exact(&mut length)?;
+
+    // The record is read up to its length rather than into a buffer of that
+    // length, as a (64-bit) length beyond the end of the file would otherwise
+    // be allocated before the read fails.
+    let length = u64::from_le_bytes(length);
+    let mut data = Vec::new();
+    reader.take(length).read_to_end(&mut data)?;
+
+    if data.len() as u64 != length {
+        return Err("truncated record".into());
+    }
+
+    Ok(Some((op[0], data)))
+}
+
+/// A reader of MCAP files.
+///
+/// If the file provides a summary section, then the set of schemas, channels,
+/// and chunks is read from the summary such that only the chunks with a
+/// message of a selected channel are read; otherwise, the data section is
+/// read from start to end.
+pub struct McapReader<R: Read + Seek> {
+    reader: R,
+    pub schemas: BTreeMap<u16, Schema>,
+    pub channels: BTreeMap<u16, Channel>,
+    chunks: Vec<ChunkIndex>,
+}
+
+impl<R: Read + Seek> McapReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut magic = [0u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+
+        if magic != MAGIC {
+            return Err("not an MCAP file".into());
+        }
+
+        match record(&mut reader)? {
+            Some((OP_HEADER, _)) => {}
+            _ => return Err("missing MCAP header".into()),
+        }
+
+        let start = reader.stream_position()?;
+
+        let mut mcap = McapReader {
+            reader,
+            schemas: BTreeMap::new(),
+            channels: BTreeMap::new(),
+            chunks: Vec::new(),
+        };
+
+        // A file that was not closed properly does not provide a footer (nor
+        // the trailing magic bytes); therefore, it is treated as one without
+        // a summary.
+        let end = mcap.reader.seek(SeekFrom::End(0))?;
+
+        let mut magic = [0u8; MAGIC.len()];
+
+        if end >= start + FOOTER_LENGTH + MAGIC.len() as u64 {
+            mcap.reader.seek(SeekFrom::End(-(MAGIC.len() as i64)))?;
+            mcap.reader.read_exact(&mut magic)?;
+        }
+
+        if magic == MAGIC {
+            mcap.reader
+                .seek(SeekFrom::Start(end - FOOTER_LENGTH - MAGIC.len() as u64))?;
+
+            if let Some((OP_FOOTER, data)) = record(&mut mcap.reader)? {
+                let summary = Fields { buf: &data }.u64()?;
+
+                if summary > 0 {
+                    mcap.summary(summary)?;
+                }
+            }
+        }
+
+        mcap.reader.seek(SeekFrom::Start(start))?;
+
+        Ok(mcap)
+    }
+
+    /// Read the summary section (i.e., up to the footer).
+    fn summary(&mut self, offset: u64) -> Result<(), Box<dyn Error>> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        while let Some((op, data)) = record(&mut self.reader)? {
+            match op {
+                OP_SCHEMA | OP_CHANNEL => self.definition(op, &data)?,
+                OP_CHUNK_INDEX => {
+                    let mut fields = Fields { buf: &data };
+
+                    // Skip the start and end time of the messages.
+                    fields.take(16)?;
+
+                    let offset = fields.u64()?;
+                    fields.u64()?;
+
+                    let mut offsets = Fields {
+                        buf: fields.bytes()?,
+                    };
+
+                    let mut channels = BTreeSet::new();
+
+                    while !offsets.buf.is_empty() {
+                        channels.insert(offsets.u16()?);
+                        offsets.u64()?;
+                    }
+
+                    self.chunks.push(ChunkIndex { offset, channels });
+                }
+                OP_FOOTER => break,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect a schema or channel record.
+    fn definition(&mut self, op: u8, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        let mut fields = Fields { buf: data };
+
+        match op {
+            OP_SCHEMA => {
+                let id = fields.u16()?;
+
+                self.schemas.entry(id).or_insert(Schema {
+                    id,
+                    name: fields.string()?,
+                    encoding: fields.string()?,
+                    data: fields.bytes()?.to_vec(),
+                });
+            }
+            OP_CHANNEL => {
+                let id = fields.u16()?;
+
+                self.channels.entry(id).or_insert(Channel {
+                    id,
+                    schema: fields.u16()?,
+                    topic: fields.string()?,
+                    encoding: fields.string()?,
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Decompress the records of a chunk.
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut fields = Fields { buf: data };
+
+        // Skip the start and end time of the messages.
+        fields.take(16)?;
+
+        let size = fields.u64()?;
+        fields.u32()?;
+
+        let compression = fields.string()?;
+        let length = fields.u64()? as usize;
+        let records = fields.take(length)?;
+
+        // The decompressed data is bounded by the (uncompressed) size of the
+        // chunk rather than allocated up front.
+        let mut data = Vec::new();
+
+        match compression.as_str() {
+            "" => return Ok(records.to_vec()),
+            "zstd" => {
+                zstd::stream::read::Decoder::new(records)?
+                    .take(size)
+                    .read_to_end(&mut data)?;
+            }
+            "lz4" => {
+                lz4_flex::frame::FrameDecoder::new(records)
+                    .take(size)
+                    .read_to_end(&mut data)?;
+            }
+            x => return Err(format!("unsupported chunk compression `{}`", x).into()),
+        }
+
+        if data.len() as u64 != size {
+            return Err("chunk size mismatch".into());
+        }
+
+        Ok(data)
+    }
+
+    /// Read the set of records of a chunk or, otherwise, the record itself.
+    fn records(&self, op: u8, data: Vec<u8>) -> Result<Vec<Record>, Box<dyn Error>> {
+        if op != OP_CHUNK {
+            return Ok(vec![(op, data)]);
+        }
+
+        let mut records = Vec::new();
+        let data = self.decompress(&data)?;
+        let mut reader = &data[..];
+
+        while let Some(x) = record(&mut reader)? {
+            records.push(x);
+        }
+
+        Ok(records)
+    }
+
+    fn message(&self, data: &[u8]) -> Result<Message, Box<dyn Error>> {
+        let mut fields = Fields { buf: data };
+
+        let channel = fields.u16()?;
+        fields.u32()?;
+
+        let time = fields.u64()? as i64;
+        fields.u64()?;
+
+        Ok(Message {
+            channel,
+            time,
+            data: fields.buf.to_vec(),
+        })
+    }
+
+    /// Check if a channel (and its schema) passes the filter.
+    ///
+    /// A channel without a schema (or an unknown one) never passes the filter.
+    fn keep<F>(&self, id: u16, filter: &F) -> bool
+    where
+        F: Fn(&Channel, &Schema) -> bool,
+    {
+        self.channels
+            .get(&id)
+            .and_then(|x| Some((x, self.schemas.get(&x.schema)?)))
+            .map(|(channel, schema)| filter(channel, schema))
+            .unwrap_or(false)
+    }
+
+    /// Read the set of messages of the channels that pass the filter.
+    pub fn messages<F>(&mut self, filter: F) -> Result<Vec<Message>, Box<dyn Error>>
+    where
+        F: Fn(&Channel, &Schema) -> bool,
+    {
+        let mut records = Vec::new();
+
+        if self.chunks.is_empty() {
+            while let Some((op, data)) = record(&mut self.reader)? {
+                if op == OP_DATA_END || op == OP_FOOTER {
+                    break;
+                }
+
+                records.extend(self.records(op, data)?);
+            }
+        } else {
+            let offsets = self
+                .chunks
+                .iter()
+                .filter(|x| {
+                    x.channels.is_empty() || x.channels.iter().any(|x| self.keep(*x, &filter))
+                })
+                .map(|x| x.offset)
+                .collect::<Vec<u64>>();
+
+            for offset in offsets {
+                self.reader.seek(SeekFrom::Start(offset))?;
+
+                let (op, data) = record(&mut self.reader)?.ok_or("missing chunk")?;
+                records.extend(self.records(op, data)?);
+            }
+        }
+
+        let mut res = Vec::new();
+
+        for (op, data) in records {
+            match op {
+                OP_SCHEMA | OP_CHANNEL => self.definition(op, &data)?,
+                OP_MESSAGE => {
+                    let message = self.message(&data)?;
+
+                    if !self.channels.contains_key(&message.channel) {
+                        return Err(
+                            format!("message of unknown channel `{}`", message.channel).into()
+                        );
+                    }
+
+                    if self.keep(message.channel, &filter) {
+                        res.push(message);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn record(op: u8, data: &[u8]) -> Vec<u8> {
+        let mut buf = vec![op];
+        buf.extend((data.len() as u64).to_le_bytes());
+        buf.extend(data);
+
+        buf
+    }
+
+    fn string(x: &str) -> Vec<u8> {
+        [&(x.len() as u32).to_le_bytes()[..], x.as_bytes()].concat()
+    }
+
+    /// Construct a file without a summary (i.e., an unindexed file).
+    fn mcap(records: &[Vec<u8>]) -> Vec<u8> {
+        let schema = [
+            &1u16.to_le_bytes()[..],
+            &string("pkg/msg/T"),
+            &string("ros2msg"),
+            &string(""),
+        ]
+        .concat();
+
+        let channel = [
+            &2u16.to_le_bytes()[..],
+            &1u16.to_le_bytes(),
+            &string("/detections"),
+            &string("cdr"),
+        ]
+        .concat();
+
+        [
+            MAGIC.to_vec(),
+            record(OP_HEADER, &[string("")].concat()),
+            record(OP_SCHEMA, &schema),
+            record(OP_CHANNEL, &channel),
+            records.concat(),
+        ]
+        .concat()
+    }
+
+    fn message(channel: u16, time: u64) -> Vec<u8> {
+        let data = [
+            &channel.to_le_bytes()[..],
+            &0u32.to_le_bytes(),
+            &time.to_le_bytes(),
+            &time.to_le_bytes(),
+            &[1, 2, 3],
+        ]
+        .concat();
+
+        record(OP_MESSAGE, &data)
+    }
+
+    #[test]
+    fn messages() {
+        let buf = mcap(&[message(2, 5), message(2, 7)]);
+        let mut reader = McapReader::new(Cursor::new(buf)).unwrap();
+
+        let messages = reader
+            .messages(|channel, schema| channel.topic == "/detections" && schema.id == 1)
+            .unwrap();
+
+        assert_eq!(
+            messages.iter().map(|x| x.time).collect::<Vec<i64>>(),
+            vec![5, 7]
+        );
+        assert_eq!(messages[0].data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unknown_channel() {
+        let buf = mcap(&[message(3, 5)]);
+        let mut reader = McapReader::new(Cursor::new(buf)).unwrap();
+
+        assert!(reader.messages(|_, _| true).is_err());
+    }
+
+    #[test]
+    fn length_overflow() {
+        let mut buf = mcap(&[]);
+        buf.push(OP_MESSAGE);
+        buf.extend(u64::MAX.to_le_bytes());
+
+        let mut reader = McapReader::new(Cursor::new(buf)).unwrap();
+
+        assert!(reader.messages(|_, _| true).is_err());
+    }
+}
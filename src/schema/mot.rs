@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::{Metadata, Schema};
+
+use self::label::Label as MotLabel;
+use self::options::Options as MotOptions;
+use self::sequence::Sequence as MotSequence;
+
+mod label;
+mod options;
+mod sequence;
+
+type FrameId = usize;
+
+/// The MOTChallenge (MOT16/17/20) benchmarks.
+///
+/// The root may either be a single sequence (i.e., containing `seqinfo.ini`)
+/// or a directory of sequences (e.g., `MOT17/train`). The frame rate and image
+/// size of each sequence are provided as stream-level metadata.
+pub struct Mot<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Mot<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", MotDebug::from(msg));
+        }
+    }
+
+    /// Collect the set of sequence directories.
+    fn sequences(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if self.root.join("seqinfo.ini").is_file() {
+            return Ok(vec![self.root.clone()]);
+        }
+
+        let mut sequences = Vec::new();
+
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+
+            if path.join("seqinfo.ini").is_file() {
+                sequences.push(path);
+            }
+        }
+
+        sequences.sort();
+
+        Ok(sequences)
+    }
+
+    /// Load the `seqinfo.ini` of a sequence.
+    fn info(&self, dir: &Path) -> Result<MotSequence, Box<dyn Error>> {
+        let path = dir.join("seqinfo.ini");
+
+        let data = fs::read_to_string(&path).or(Err(Box::new(MotError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        Ok(data
+            .parse()
+            .map_err(|e| MotError::from(format!("{}: {}", path.display(), e)))?)
+    }
+
+    /// Load the set of [`MotLabel`] of a `gt.txt` or `det.txt` file, grouped
+    /// by frame.
+    fn load(&self, path: &Path) -> Result<BTreeMap<FrameId, Vec<MotLabel>>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(MotError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut labels: BTreeMap<FrameId, Vec<MotLabel>> = BTreeMap::new();
+
+        for (i, line) in BufReader::new(infile).lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let label: MotLabel = line
+                .parse()
+                .map_err(|e| MotError::from(format!("{}:{}: {}", path.display(), i + 1, e)))?;
+
+            labels.entry(label.frame).or_default().push(label);
+        }
+
+        self.debug(&format!("read labels from `{}`", path.display()));
+
+        Ok(labels)
+    }
+
+    /// Map the MOTChallenge class identifier to its name.
+    ///
+    /// The detection files do not provide a class as every detection is
+    /// assumed to be a pedestrian.
+    fn class(&self, class: Option<u32>) -> String {
+        String::from(match class {
+            None | Some(1) => "pedestrian",
+            Some(2) => "person_on_vehicle",
+            Some(3) => "car",
+            Some(4) => "bicycle",
+            Some(5) => "motorbike",
+            Some(6) => "non_mot_vehicle",
+            Some(7) => "static_person",
+            Some(8) => "distractor",
+            Some(9) => "occluder",
+            Some(10) => "occluder_on_ground",
+            Some(11) => "occluder_full",
+            Some(12) => "reflection",
+            Some(13) => "crowd",
+            Some(_) => "unknown",
+        })
+    }
+
+    fn image(&self, dir: &Path, sequence: &MotSequence, frame: FrameId) -> Image {
+        let source = ImageSource::File(
+            dir.join(&sequence.directory)
+                .join(format!("{:06}{}", frame, sequence.extension)),
+        );
+
+        Image::new(source, sequence.width, sequence.height)
+    }
+
+    fn annotations(
+        &self,
+        labels: &[MotLabel],
+        gt: bool,
+        options: &MotOptions,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for label in labels.iter().filter(|x| options.keep(x, gt)) {
+            let [left, top, width, height] = label.bbox;
+            let class = self.class(label.class);
+
+            res.entry(class.clone()).or_default().push(Annotation::new(
+                class,
+                label.conf,
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(left + (width / 2.0), top + (height / 2.0)),
+                    width,
+                    height,
+                )),
+            ));
+        }
+
+        res
+    }
+}
+
+impl Schema for Mot<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        Ok(self.import_with_metadata()?.0)
+    }
+
+    fn import_with_metadata(
+        &self,
+    ) -> Result<(Vec<(String, Vec<Frame>)>, Metadata), Box<dyn Error>> {
+        let mut metadata = Metadata::new();
+
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: MotOptions = self.config.options("mot")?;
+
+        let mut datastreams = Vec::new();
+
+        for dir in self.sequences()? {
+            let sequence = self.info(&dir)?;
+
+            // Load each of the requested channels.
+            //
+            // The ground truth and public detections are kept as separate
+            // channels of the same stream such that they may be compared.
+            let mut channels = Vec::new();
+
+            for name in options.channels.iter() {
+                let path = dir.join(name).join(format!("{}.txt", name));
+
+                if path.is_file() {
+                    channels.push((name == "gt", format!("cam::{}", name), self.load(&path)?));
+                }
+            }
+
+            // Construct the set of [`Frame`].
+            //
+            // The frames of MOTChallenge are numbered from one; therefore, each
+            // frame of the sequence is inserted such that no gaps exist.
+            let mut frames = Vec::new();
+
+            for (index, id) in (1..=sequence.length).enumerate() {
+                let mut frame = Frame::new(index);
+
+                for (gt, channel, data) in channels.iter() {
+                    let mut record = DetectionRecord::new(
+                        channel.clone(),
+                        Some(self.image(&dir, &sequence, id)),
+                    );
+
+                    if let Some(labels) = data.get(&id) {
+                        record.annotations = self.annotations(labels, *gt, &options);
+                    }
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            metadata.insert(
+                sequence.name.clone(),
+                serde_json::json!({
+                    "rate": sequence.rate,
+                    "width": sequence.width,
+                    "height": sequence.height,
+                }),
+            );
+
+            self.debug(&format!("imported stream `{}`", sequence.name));
+            datastreams.push((sequence.name, frames));
+        }
+
+        Ok((datastreams, metadata))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MotDebug {
+    msg: String,
+}
+
+impl From<&str> for MotDebug {
+    fn from(msg: &str) -> Self {
+        MotDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for MotDebug {
+    fn from(msg: String) -> Self {
+        MotDebug { msg }
+    }
+}
+
+impl fmt::Display for MotDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: mot: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MotError {
+    msg: String,
+}
+
+impl From<&str> for MotError {
+    fn from(msg: &str) -> Self {
+        MotError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for MotError {
+    fn from(msg: String) -> Self {
+        MotError { msg }
+    }
+}
+
+impl fmt::Display for MotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "mot: {}", self.msg)
+    }
+}
+
+impl Error for MotError {}
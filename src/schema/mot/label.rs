@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::str::FromStr;
+
+/// A single line of a MOTChallenge `gt.txt` or `det.txt` file.
+///
+/// The ground truth files use the class and visibility columns whereas the
+/// detection files leave them unset (or set to `-1`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub frame: usize,
+    pub id: i64,
+    /// left, top, width, height (in pixels)
+    pub bbox: [f64; 4],
+    pub conf: f64,
+    pub class: Option<u32>,
+    pub visibility: Option<f64>,
+}
+
+impl FromStr for Label {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(',').map(|x| x.trim()).collect();
+
+        if fields.len() < 7 {
+            return Err(format!("expected at least 7 fields, found {}", fields.len()).into());
+        }
+
+        // The class and visibility columns only exist in the ground truth
+        // files with exactly 9 columns. The detection files instead have the
+        // (unused) world coordinates in the same position.
+        let (class, visibility) = if fields.len() == 9 {
+            (
+                Some(fields[7].parse::<u32>()?),
+                Some(fields[8].parse::<f64>()?),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(Label {
+            frame: fields[0].parse()?,
+            id: fields[1].parse::<f64>()? as i64,
+            bbox: [
+                fields[2].parse()?,
+                fields[3].parse()?,
+                fields[4].parse()?,
+                fields[5].parse()?,
+            ],
+            conf: fields[6].parse()?,
+            class,
+            visibility,
+        })
+    }
+}
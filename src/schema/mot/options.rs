@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+use super::label::Label;
+
+/// The set of options applied to MOTChallenge sequences.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The minimum `conf` of an object to keep.
+    pub conf: Option<f64>,
+    /// The minimum `visibility` of an object to keep (ground truth only).
+    pub visibility: Option<f64>,
+    /// The set of files to import as channels (i.e., `gt` and/or `det`).
+    pub channels: Vec<String>,
+    /// Keep the ground truth objects with a `conf` of `0` (i.e., ignored).
+    pub ignored: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            conf: None,
+            visibility: None,
+            channels: vec![String::from("gt"), String::from("det")],
+            ignored: false,
+        }
+    }
+}
+
+impl Options {
+    /// Check if the [`Label`] passes the set of filters.
+    ///
+    /// The `conf` of the ground truth is a flag where `0` marks an object to
+    /// be ignored during evaluation; therefore, these are dropped unless
+    /// requested otherwise.
+    pub fn keep(&self, label: &Label, gt: bool) -> bool {
+        if gt && label.conf == 0.0 && !self.ignored {
+            return false;
+        }
+
+        if let Some(conf) = self.conf {
+            if label.conf < conf {
+                return false;
+            }
+        }
+
+        if let (Some(visibility), Some(x)) = (self.visibility, label.visibility) {
+            if x < visibility {
+                return false;
+            }
+        }
+
+        true
+    }
+}
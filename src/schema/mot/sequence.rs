@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::str::FromStr;
+
+/// The `[Sequence]` section of a `seqinfo.ini` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sequence {
+    pub name: String,
+    pub directory: String,
+    /// The frame rate (in frames per second).
+    pub rate: f64,
+    pub length: usize,
+    pub width: u32,
+    pub height: u32,
+    pub extension: String,
+}
+
+impl FromStr for Sequence {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut name = None;
+        let mut directory = None;
+        let mut rate = None;
+        let mut length = None;
+        let mut width = None;
+        let mut height = None;
+        let mut extension = None;
+
+        for line in s.lines().map(|x| x.trim()) {
+            if line.is_empty() || line.starts_with(['[', ';', '#']) {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("invalid line `{}`", line))?;
+
+            let value = value.trim();
+
+            match key.trim() {
+                "name" => name = Some(value.to_string()),
+                "imDir" => directory = Some(value.to_string()),
+                "frameRate" => rate = Some(value.parse::<f64>()?),
+                "seqLength" => length = Some(value.parse::<usize>()?),
+                "imWidth" => width = Some(value.parse::<u32>()?),
+                "imHeight" => height = Some(value.parse::<u32>()?),
+                "imExt" => extension = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(Sequence {
+            name: name.ok_or("missing `name`")?,
+            directory: directory.unwrap_or_else(|| String::from("img1")),
+            rate: rate.ok_or("missing `frameRate`")?,
+            length: length.ok_or("missing `seqLength`")?,
+            width: width.ok_or("missing `imWidth`")?,
+            height: height.ok_or("missing `imHeight`")?,
+            extension: extension.unwrap_or_else(|| String::from(".jpg")),
+        })
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{Annotation, DetectionRecord};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::nuscenes::calibration::Calibration as NuCalibration;
+use super::nuscenes::category::Category as NuCategory;
+use super::nuscenes::data::Data as NuData;
+use super::nuscenes::sensor::Sensor as NuSensor;
+use super::nuscenes::{NuScenes, Variant};
+use super::Schema;
+
+use self::attribute::Attribute as NuAttribute;
+use self::object::Object as NuObject;
+use self::options::Options as NuImagesOptions;
+use self::sample::Sample as NuSample;
+use self::surface::Surface as NuSurface;
+
+mod attribute;
+mod mask;
+mod object;
+mod options;
+mod sample;
+mod surface;
+
+type DataToken = String;
+type AttributeToken = String;
+type CategoryToken = String;
+type CalibrationToken = String;
+type SensorToken = String;
+
+/// The nuImages dataset.
+///
+/// The tables follow the NuScenes layout; however, the objects are annotated
+/// with 2D boxes directly on the key camera image of each sample. The surface
+/// annotations (i.e., masks only) are optionally imported as the bounding box
+/// of their mask, and the object attributes may be used to filter objects.
+pub struct NuImages<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> NuImages<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", NuImagesDebug::from(msg));
+        }
+    }
+
+    /// Collect the sweeps surrounding the key camera data.
+    ///
+    /// This follows the `prev` chain to the first sweep and then the `next`
+    /// chain to the last sweep such that the sweeps are in order.
+    fn sweeps<'b>(
+        &self,
+        key: &'b NuData,
+        datas: &'b HashMap<DataToken, NuData>,
+    ) -> Vec<&'b NuData> {
+        let mut first = key;
+
+        while let Some(data) = datas.get(&first.prev) {
+            first = data;
+        }
+
+        let mut sweeps = vec![first];
+
+        while let Some(data) = datas.get(&sweeps[sweeps.len() - 1].next) {
+            sweeps.push(data);
+        }
+
+        sweeps
+    }
+
+    /// Convert the set of objects (and surfaces) into annotations.
+    ///
+    /// The objects with any of the attributes to drop are skipped. The surfaces
+    /// are annotated with the bounding box of their mask, if not empty.
+    fn annotations(
+        &self,
+        objects: &[NuObject],
+        surfaces: &[NuSurface],
+        categories: &HashMap<CategoryToken, NuCategory>,
+        attributes: &HashMap<AttributeToken, NuAttribute>,
+        options: &NuImagesOptions,
+    ) -> Result<HashMap<String, Vec<Annotation>>, Box<dyn Error>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        let mut boxes = Vec::new();
+
+        for object in objects {
+            let drop = object.attribute_tokens.iter().any(|x| {
+                attributes
+                    .get(x)
+                    .map(|x| options.attributes.contains(&x.name))
+                    .unwrap_or(false)
+            });
+
+            if !drop {
+                boxes.push((&object.category_token, object.bbox));
+            }
+        }
+
+        for surface in surfaces {
+            if let Some(mask) = &surface.mask {
+                if let Some(bbox) = mask.bbox()? {
+                    boxes.push((&surface.category_token, bbox));
+                }
+            }
+        }
+
+        for (category, [xmin, ymin, xmax, ymax]) in boxes {
+            let label = categories.get(category).unwrap();
+
+            let width = xmax - xmin;
+            let height = ymax - ymin;
+
+            res.entry(label.name.clone())
+                .or_default()
+                .push(Annotation::new(
+                    label.name.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(aa::Region::new(
+                        Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
+                        width,
+                        height,
+                    )),
+                ));
+        }
+
+        Ok(res)
+    }
+}
+
+impl Schema for NuImages<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: NuImagesOptions = self.config.options("nuimages")?;
+        let channels = options
+            .nuscenes
+            .channels
+            .clone()
+            .unwrap_or_else(|| Variant::NuScenes.channels());
+
+        // Set up internal database.
+        //
+        // The tables are loaded through the NuScenes importer as both datasets
+        // share the same token-based layout.
+        self.debug("building internal database");
+
+        let nuscenes = NuScenes::new(&self.root, self.config);
+
+        let samples = nuscenes.load::<NuSample>(&options.nuscenes.table("sample"))?;
+
+        let datas: HashMap<DataToken, NuData> = nuscenes
+            .load::<NuData>(&options.nuscenes.table("sample_data"))?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        // There are multiple [`NuObject`] per sample data.
+        //
+        // Therefore, a mapping between a sample data and its associated set of
+        // [`NuObject`] must be created.
+        let mut objects: HashMap<DataToken, Vec<NuObject>> = HashMap::new();
+
+        for o in nuscenes.load::<NuObject>(&options.nuscenes.table("object_ann"))? {
+            let token = o.sample_data_token.clone();
+            objects.entry(token).or_default().push(o);
+        }
+
+        // The surface annotations are only loaded if requested as these are
+        // masks (i.e., without a bounding box) that must be decoded.
+        let mut surfaces: HashMap<DataToken, Vec<NuSurface>> = HashMap::new();
+
+        if options.surfaces {
+            for s in nuscenes.load::<NuSurface>(&options.nuscenes.table("surface_ann"))? {
+                let token = s.sample_data_token.clone();
+                surfaces.entry(token).or_default().push(s);
+            }
+        }
+
+        let attributes: HashMap<AttributeToken, NuAttribute> = nuscenes
+            .load::<NuAttribute>(&options.nuscenes.table("attribute"))?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        let categories: HashMap<CategoryToken, NuCategory> = nuscenes
+            .load::<NuCategory>(&options.nuscenes.table("category"))?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        let calibrations: HashMap<CalibrationToken, NuCalibration> = nuscenes
+            .load::<NuCalibration>(&options.nuscenes.table("calibrated_sensor"))?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        let sensors: HashMap<SensorToken, NuSensor> = nuscenes
+            .load::<NuSensor>(&options.nuscenes.table("sensor"))?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each sample and collect the sweeps of the key
+        // camera into a short linear stream where only the key frame is
+        // annotated.
+        let mut datastreams = Vec::new();
+
+        for sample in samples.iter() {
+            let key = datas.get(&sample.key_camera_token).ok_or_else(|| {
+                NuImagesError::from(format!("{}: missing key camera data", sample.token))
+            })?;
+
+            let calibration = calibrations.get(&key.calibrated_sensor_token).unwrap();
+            let sensor = sensors.get(&calibration.sensor_token).unwrap();
+
+            // If [`Some`] mapping exists, proceed.
+            //
+            // The channel table is used to filter out samples of cameras that
+            // we do not want to consider.
+            let channel = match channels.get(&sensor.channel) {
+                Some(channel) => channel,
+                None => continue,
+            };
+
+            let mut frames = Vec::new();
+
+            for (index, data) in self.sweeps(key, &datas).into_iter().enumerate() {
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(channel.clone(), nuscenes.image(data));
+
+                record.annotations = self
+                    .annotations(
+                        objects
+                            .get(&data.token)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                        surfaces
+                            .get(&data.token)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default(),
+                        &categories,
+                        &attributes,
+                        &options,
+                    )
+                    .map_err(|e| NuImagesError::from(format!("{}: {}", data.token, e)))?;
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", sample.token));
+            datastreams.push((sample.token.clone(), frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NuImagesDebug {
+    msg: String,
+}
+
+impl From<&str> for NuImagesDebug {
+    fn from(msg: &str) -> Self {
+        NuImagesDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for NuImagesDebug {
+    fn from(msg: String) -> Self {
+        NuImagesDebug { msg }
+    }
+}
+
+impl fmt::Display for NuImagesDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: nuimages: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NuImagesError {
+    msg: String,
+}
+
+impl From<&str> for NuImagesError {
+    fn from(msg: &str) -> Self {
+        NuImagesError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for NuImagesError {
+    fn from(msg: String) -> Self {
+        NuImagesError { msg }
+    }
+}
+
+impl fmt::Display for NuImagesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "nuimages: {}", self.msg)
+    }
+}
+
+impl Error for NuImagesError {}
@@ -0,0 +1,169 @@
+use std::error::Error;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+
+/// A run-length encoded mask of the nuImages dataset.
+///
+/// The `counts` are the COCO compressed run-length encoding of the mask (in
+/// column-major order), additionally encoded as base64.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Mask {
+    /// height, width
+    pub size: [u64; 2],
+    pub counts: String,
+}
+
+impl Mask {
+    /// Decode the runs of the mask, alternating between zeros and ones.
+    ///
+    /// This follows `rleFrString` of the COCO API where each run is stored as
+    /// a set of 6-bit characters (offset by `48`) and, after the first two,
+    /// each run is stored as the difference to the run two before it.
+    fn runs(&self) -> Result<Vec<u64>, Box<dyn Error>> {
+        let s = STANDARD.decode(&self.counts)?;
+
+        let mut runs: Vec<i64> = Vec::new();
+        let mut p = 0;
+
+        while p < s.len() {
+            let mut x = 0i64;
+            let mut k = 0;
+
+            loop {
+                let c = (*s.get(p).ok_or("truncated mask")? as i64) - 48;
+
+                if !(0..64).contains(&c) || k >= 12 {
+                    return Err("malformed mask".into());
+                }
+
+                x |= (c & 0x1f) << (5 * k);
+                p += 1;
+                k += 1;
+
+                if c & 0x20 == 0 {
+                    if c & 0x10 != 0 {
+                        x |= -1 << (5 * k);
+                    }
+
+                    break;
+                }
+            }
+
+            if runs.len() > 2 {
+                x += runs[runs.len() - 2];
+            }
+
+            if x < 0 {
+                return Err("malformed mask".into());
+            }
+
+            runs.push(x);
+        }
+
+        Ok(runs.into_iter().map(|x| x as u64).collect())
+    }
+
+    /// The bounding box (i.e., xmin, ymin, xmax, ymax) of the mask, if any.
+    ///
+    /// The box encloses each pixel of the mask such that a single pixel at
+    /// `(x, y)` results in `[x, y, x + 1, y + 1]`.
+    pub fn bbox(&self) -> Result<Option<[f64; 4]>, Box<dyn Error>> {
+        let [height, width] = self.size;
+
+        let mut min = [u64::MAX; 2];
+        let mut max = [0u64; 2];
+        let mut pos = 0u64;
+
+        for (i, run) in self.runs()?.into_iter().enumerate() {
+            let start = pos;
+            pos = pos.checked_add(run).ok_or("malformed mask")?;
+
+            if i % 2 == 0 || run == 0 {
+                continue;
+            }
+
+            if pos > height * width {
+                return Err("mask exceeds its size".into());
+            }
+
+            let end = pos - 1;
+            let (x0, x1) = (start / height, end / height);
+
+            // A run spanning multiple columns covers each row in between.
+            let (y0, y1) = if x0 == x1 {
+                (start % height, end % height)
+            } else {
+                (0, height - 1)
+            };
+
+            min = [min[0].min(x0), min[1].min(y0)];
+            max = [max[0].max(x1), max[1].max(y1)];
+        }
+
+        if min[0] == u64::MAX {
+            return Ok(None);
+        }
+
+        Ok(Some([
+            min[0] as f64,
+            min[1] as f64,
+            (max[0] + 1) as f64,
+            (max[1] + 1) as f64,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs() {
+        let mask = Mask {
+            size: [4, 5],
+            counts: String::from("NTIyTzIwMQ=="),
+        };
+
+        assert_eq!(mask.runs().unwrap(), vec![5, 2, 2, 1, 4, 1, 5]);
+        assert_eq!(mask.bbox().unwrap(), Some([1.0, 1.0, 4.0, 3.0]));
+    }
+
+    #[test]
+    fn bbox() {
+        // A 50x90 rectangle at (30, 10) and a single pixel in the corner.
+        let mask = Mask {
+            size: [100, 200],
+            counts: String::from(concat!(
+                "Um4yYjFiMTAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAw",
+                "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAw",
+                "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAw",
+                "MDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMGVpN19O",
+            )),
+        };
+
+        assert_eq!(mask.runs().unwrap().len(), 182);
+        assert_eq!(mask.bbox().unwrap(), Some([30.0, 10.0, 200.0, 100.0]));
+    }
+
+    #[test]
+    fn empty() {
+        let mask = Mask {
+            size: [4, 5],
+            counts: String::from("ZDA="),
+        };
+
+        assert_eq!(mask.bbox().unwrap(), None);
+    }
+
+    #[test]
+    fn malformed() {
+        let mask = Mask {
+            size: [4, 5],
+            counts: String::from("fg=="),
+        };
+
+        assert!(mask.bbox().is_err());
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Object {
+    pub sample_data_token: String,
+    pub category_token: String,
+    #[serde(default)]
+    pub attribute_tokens: Vec<String>,
+    /// xmin, ymin, xmax, ymax
+    pub bbox: [f64; 4],
+}
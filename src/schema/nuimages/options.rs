@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+use crate::schema::nuscenes::options::Options as NuOptions;
+
+/// The set of options applied to the nuImages dataset.
+///
+/// This extends the options of NuScenes-formatted datasets (i.e., the channel
+/// and table mappings) with the nuImages specific filters.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    #[serde(flatten)]
+    pub nuscenes: NuOptions,
+    /// The set of attribute names (e.g., `vehicle.parked`) of objects to drop.
+    pub attributes: Vec<String>,
+    /// Import the surface annotations as the bounding box of their mask.
+    pub surfaces: bool,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Sample {
+    pub token: String,
+    pub key_camera_token: String,
+}
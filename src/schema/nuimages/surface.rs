@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+use super::mask::Mask;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Surface {
+    pub sample_data_token: String,
+    pub category_token: String,
+    pub mask: Option<Mask>,
+}
@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nalgebra::{RowSVector as StaticRowVector, SMatrix as StaticMatrix};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::cuboid::Cuboid;
+use super::nuscenes::calibration::Calibration as NuCalibration;
+use super::nuscenes::ego::Ego as NuEgo;
+use super::nuscenes::NuScenes;
+use super::timeline;
+use super::Schema;
+
+use self::database::{
+    Camera as PlanCamera, Database, EgoPose as PlanEgoPose, Image as PlanImage,
+    LidarBox as PlanLidarBox, LidarPc as PlanLidarPc, Token,
+};
+use self::options::Options as PlanOptions;
+
+mod database;
+mod options;
+
+type Timestamp = i64;
+
+/// The nominal period of the cameras (i.e., 10Hz in microseconds).
+const PERIOD: Timestamp = 100_000;
+
+/// A camera of a log (i.e., its channel, calibration, and images).
+struct PlanSensor<'a> {
+    channel: String,
+    camera: &'a PlanCamera,
+    calibration: NuCalibration,
+    images: BTreeMap<Timestamp, &'a PlanImage>,
+    /// The maximum offset of an image from a sweep (i.e., half the period).
+    tolerance: Timestamp,
+}
+
+/// The nuPlan dataset.
+///
+/// The input may either be a single log (i.e., a `.db` SQLite file) or a
+/// directory of logs. Each log (or each scene of a log) forms a stream where
+/// each lidar sweep forms a [`Frame`] and the 3D boxes of the sweep are
+/// projected onto each camera.
+pub struct NuPlan<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> NuPlan<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", NuPlanDebug::from(msg));
+        }
+    }
+
+    /// The default mapping of cameras to channels.
+    fn channels(&self) -> HashMap<String, String> {
+        HashMap::from(
+            [
+                ("CAM_F0", "cam::front"),
+                ("CAM_L0", "cam::front::left"),
+                ("CAM_R0", "cam::front::right"),
+                ("CAM_L1", "cam::side::left"),
+                ("CAM_R1", "cam::side::right"),
+                ("CAM_L2", "cam::back::left"),
+                ("CAM_R2", "cam::back::right"),
+                ("CAM_B0", "cam::back"),
+            ]
+            .map(|(camera, channel)| (String::from(camera), String::from(channel))),
+        )
+    }
+
+    /// The set of log files to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "db").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    fn open(&self, path: &Path) -> Result<Database, Box<dyn Error>> {
+        Database::open(path).map_err(|e| {
+            NuPlanError::from(format!("unable to open `{}`: {}", path.display(), e)).into()
+        })
+    }
+
+    fn image(&self, camera: &PlanCamera, image: &PlanImage, options: &PlanOptions) -> Image {
+        let path = match &options.images {
+            Some(dir) => dir.join(&image.filename),
+            None => PathBuf::from(&image.filename),
+        };
+
+        Image::new(ImageSource::File(path), camera.width, camera.height)
+    }
+
+    /// Project the set of boxes of a sweep onto a camera.
+    ///
+    /// The boxes are with respect to the global coordinate system; therefore,
+    /// the same chain of transforms as nuScenes applies (i.e., into the
+    /// ego-vehicle at the time of the image and then into the camera).
+    fn annotations(
+        &self,
+        boxes: &[&PlanLidarBox],
+        ego: &PlanEgoPose,
+        sensor: &PlanSensor,
+        tracks: &HashMap<Token, String>,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+        let viewport = StaticMatrix::<f64, 3, 3>::from_rows(&[
+            StaticRowVector::<f64, 3>::from(sensor.camera.intrinsic[0]),
+            StaticRowVector::<f64, 3>::from(sensor.camera.intrinsic[1]),
+            StaticRowVector::<f64, 3>::from(sensor.camera.intrinsic[2]),
+        ]);
+
+        let ego = NuEgo {
+            token: ego.token.clone(),
+            rotation: ego.rotation,
+            translation: ego.translation,
+            timestamp: ego.timestamp as f64,
+        };
+
+        for x in boxes {
+            let label = match tracks.get(&x.track) {
+                Some(label) => label,
+                None => continue,
+            };
+
+            // The heading is a rotation about the z-axis only.
+            let rotation = [(x.yaw / 2.0).cos(), 0.0, 0.0, (x.yaw / 2.0).sin()];
+            let cuboid = Cuboid::new(x.translation, x.size, rotation);
+
+            let a = NuScenes::translate(cuboid, &ego, &sensor.calibration);
+
+            if a.inside(
+                viewport,
+                sensor.camera.width as f64,
+                sensor.camera.height as f64,
+            ) {
+                res.entry(label.clone()).or_default().push(Annotation::new(
+                    label.clone(),
+                    x.confidence,
+                    BoundingBox::AxisAligned(a.region(viewport)),
+                ));
+            }
+        }
+
+        res
+    }
+
+    /// Import a single log as a set of streams.
+    fn log(
+        &self,
+        path: &Path,
+        options: &PlanOptions,
+    ) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let db = self.open(path)?;
+
+        let error = |e: Box<dyn Error>| NuPlanError::from(format!("{}: {}", name, e));
+
+        // Set up internal database.
+        //
+        // The poses, calibrations, and images of the log must first be set up
+        // in order to project the boxes onto each camera.
+        self.debug(&format!("building internal database of `{}`", name));
+
+        let sweeps: Vec<PlanLidarPc> = db.lidar_pcs().map_err(error)?;
+        let cameras: Vec<PlanCamera> = db.cameras().map_err(error)?;
+        let images: Vec<PlanImage> = db.images().map_err(error)?;
+
+        let poses: HashMap<Token, PlanEgoPose> = db
+            .ego_poses()
+            .map_err(error)?
+            .into_iter()
+            .map(|x| (x.token.clone(), x))
+            .collect();
+
+        let tracks: HashMap<Token, String> = db.tracks().map_err(error)?.into_iter().collect();
+
+        let mut boxes: HashMap<Token, Vec<PlanLidarBox>> = HashMap::new();
+
+        for x in db.lidar_boxes().map_err(error)? {
+            boxes.entry(x.lidar_pc.clone()).or_default().push(x);
+        }
+
+        let channels = options.channels.clone().unwrap_or(self.channels());
+
+        let mut sensors = Vec::new();
+
+        for camera in cameras.iter() {
+            if let Some(channel) = channels.get(&camera.channel) {
+                let calibration = NuCalibration {
+                    token: camera.token.clone(),
+                    sensor_token: camera.token.clone(),
+                    rotation: camera.rotation,
+                    translation: camera.translation,
+                    camera_intrinsic: Some(camera.intrinsic),
+                };
+
+                let images: BTreeMap<Timestamp, &PlanImage> = images
+                    .iter()
+                    .filter(|x| x.camera == camera.token)
+                    .map(|x| (x.timestamp, x))
+                    .collect();
+
+                let tolerance = timeline::tolerance(&images, PERIOD);
+
+                sensors.push(PlanSensor {
+                    channel: channel.clone(),
+                    camera,
+                    calibration,
+                    images,
+                    tolerance,
+                });
+            }
+        }
+
+        sensors.sort_by(|a, b| a.channel.cmp(&b.channel));
+
+        // Group the sweeps into streams.
+        //
+        // By default, the log forms a single stream; otherwise, each scene of
+        // the log forms its own stream and the sweeps outside of a scene are
+        // skipped, accordingly.
+        let mut groups: Vec<(String, Vec<&PlanLidarPc>)> = Vec::new();
+
+        if options.scenes {
+            let scenes: HashMap<Token, String> = db
+                .scenes()
+                .map_err(error)?
+                .into_iter()
+                .map(|x| {
+                    let label = if x.name.is_empty() {
+                        x.token.clone()
+                    } else {
+                        x.name
+                    };
+
+                    (x.token, label)
+                })
+                .collect();
+
+            let mut streams: BTreeMap<String, Vec<&PlanLidarPc>> = BTreeMap::new();
+
+            for sweep in sweeps.iter() {
+                if let Some(scene) = sweep.scene.as_ref().and_then(|x| scenes.get(x)) {
+                    streams
+                        .entry(format!("{}-{}", name, scene))
+                        .or_default()
+                        .push(sweep);
+                }
+            }
+
+            groups.extend(streams);
+        } else {
+            groups.push((name.clone(), sweeps.iter().collect()));
+        }
+
+        let mut datastreams = Vec::new();
+
+        for (stream, sweeps) in groups {
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each sweep and associate the nearest
+            // image of each camera with it, accordingly. An image more than
+            // half a period away (e.g., a dropped frame) is not associated.
+            let mut frames = Vec::new();
+
+            for (index, sweep) in sweeps.iter().enumerate() {
+                let mut frame = Frame::new(index);
+
+                let pose = poses.get(&sweep.ego_pose).ok_or_else(|| {
+                    NuPlanError::from(format!("{}: missing ego pose of sweep", stream))
+                })?;
+
+                let annotations = boxes
+                    .get(&sweep.token)
+                    .map(|x| x.iter().collect::<Vec<&PlanLidarBox>>())
+                    .unwrap_or_default();
+
+                for sensor in sensors.iter() {
+                    let image = timeline::within(&sensor.images, sweep.timestamp, sensor.tolerance);
+
+                    let (image, ego) = match image {
+                        Some((_, x)) => (
+                            Some(self.image(sensor.camera, x, options)),
+                            poses.get(&x.ego_pose).unwrap_or(pose),
+                        ),
+                        None => (None, pose),
+                    };
+
+                    let mut record = DetectionRecord::new(sensor.channel.clone(), image);
+                    record.annotations = self.annotations(&annotations, ego, sensor, &tracks);
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            datastreams.push((stream, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+impl Schema for NuPlan<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: PlanOptions = self.config.options("nuplan")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            for (name, frames) in self.log(&path, &options)? {
+                self.debug(&format!("imported stream `{}`", name));
+                datastreams.push((name, frames));
+            }
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NuPlanDebug {
+    msg: String,
+}
+
+impl From<&str> for NuPlanDebug {
+    fn from(msg: &str) -> Self {
+        NuPlanDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for NuPlanDebug {
+    fn from(msg: String) -> Self {
+        NuPlanDebug { msg }
+    }
+}
+
+impl fmt::Display for NuPlanDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: nuplan: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NuPlanError {
+    msg: String,
+}
+
+impl From<&str> for NuPlanError {
+    fn from(msg: &str) -> Self {
+        NuPlanError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for NuPlanError {
+    fn from(msg: String) -> Self {
+        NuPlanError { msg }
+    }
+}
+
+impl fmt::Display for NuPlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "nuplan: {}", self.msg)
+    }
+}
+
+impl Error for NuPlanError {}
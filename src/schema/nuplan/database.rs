@@ -0,0 +1,240 @@
+use std::error::Error;
+use std::path::Path;
+
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags, Row};
+
+/// The token (i.e., primary key) of a row, as a hexadecimal string.
+pub type Token = String;
+
+/// A single `lidar_pc` (i.e., a sweep of the lidar).
+#[derive(Clone, Debug, PartialEq)]
+pub struct LidarPc {
+    pub token: Token,
+    pub scene: Option<Token>,
+    pub ego_pose: Token,
+    /// The timestamp (in microseconds).
+    pub timestamp: i64,
+}
+
+/// A single `lidar_box` (i.e., a 3D box of a sweep).
+#[derive(Clone, Debug, PartialEq)]
+pub struct LidarBox {
+    pub lidar_pc: Token,
+    pub track: Token,
+    /// x, y, z (global)
+    pub translation: [f64; 3],
+    /// width, length, height
+    pub size: [f64; 3],
+    /// The heading about the z-axis (in radians).
+    pub yaw: f64,
+    pub confidence: f64,
+}
+
+/// A single `ego_pose`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EgoPose {
+    pub token: Token,
+    /// x, y, z
+    pub translation: [f64; 3],
+    /// w, x, y, z
+    pub rotation: [f64; 4],
+    pub timestamp: i64,
+}
+
+/// A single `camera` (i.e., its calibration).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Camera {
+    pub token: Token,
+    /// The name of the camera (e.g., `CAM_F0`).
+    pub channel: String,
+    /// x, y, z (with respect to the ego-vehicle)
+    pub translation: [f64; 3],
+    /// w, x, y, z (with respect to the ego-vehicle)
+    pub rotation: [f64; 4],
+    pub intrinsic: [[f64; 3]; 3],
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A single `image` of a camera.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub camera: Token,
+    pub ego_pose: Token,
+    /// The path to the image (relative to the sensor blobs).
+    pub filename: String,
+    pub timestamp: i64,
+}
+
+/// A single `scene` of a log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene {
+    pub token: Token,
+    pub name: String,
+}
+
+/// Encode a token (i.e., a `BLOB`) as a hexadecimal string.
+fn token(row: &Row, index: usize) -> rusqlite::Result<Token> {
+    Ok(match row.get::<_, Value>(index)? {
+        Value::Blob(x) => x.iter().map(|x| format!("{:02x}", x)).collect(),
+        Value::Text(x) => x,
+        Value::Integer(x) => x.to_string(),
+        _ => String::new(),
+    })
+}
+
+/// Decode a JSON-encoded array column (e.g., `translation`).
+///
+/// The arrays are stored as text, although some logs store the same text as a
+/// `BLOB` instead.
+fn array<T>(row: &Row, index: usize) -> rusqlite::Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let text = match row.get::<_, Value>(index)? {
+        Value::Text(x) => x,
+        Value::Blob(x) => String::from_utf8_lossy(&x).into_owned(),
+        _ => String::new(),
+    };
+
+    serde_json::from_str(&text).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// A nuPlan log (i.e., a `.db` SQLite file).
+pub struct Database {
+    connection: Connection,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Ok(Self { connection })
+    }
+
+    /// Run a query and collect each row.
+    fn query<T, F>(&self, sql: &str, f: F) -> Result<Vec<T>, Box<dyn Error>>
+    where
+        F: FnMut(&Row) -> rusqlite::Result<T>,
+    {
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement.query_map([], f)?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<T>>>()?)
+    }
+
+    /// The set of sweeps, ordered by timestamp.
+    pub fn lidar_pcs(&self) -> Result<Vec<LidarPc>, Box<dyn Error>> {
+        self.query(
+            "SELECT token, scene_token, ego_pose_token, timestamp FROM lidar_pc ORDER BY timestamp",
+            |row| {
+                Ok(LidarPc {
+                    token: token(row, 0)?,
+                    scene: match row.get::<_, Value>(1)? {
+                        Value::Null => None,
+                        _ => Some(token(row, 1)?),
+                    },
+                    ego_pose: token(row, 2)?,
+                    timestamp: row.get(3)?,
+                })
+            },
+        )
+    }
+
+    pub fn lidar_boxes(&self) -> Result<Vec<LidarBox>, Box<dyn Error>> {
+        self.query(
+            "SELECT lidar_pc_token, track_token, x, y, z, width, length, height, yaw, confidence \
+             FROM lidar_box",
+            |row| {
+                Ok(LidarBox {
+                    lidar_pc: token(row, 0)?,
+                    track: token(row, 1)?,
+                    translation: [row.get(2)?, row.get(3)?, row.get(4)?],
+                    size: [row.get(5)?, row.get(6)?, row.get(7)?],
+                    yaw: row.get(8)?,
+                    confidence: row.get::<_, Option<f64>>(9)?.unwrap_or(1.0),
+                })
+            },
+        )
+    }
+
+    /// The mapping of tracks to the name of their category.
+    pub fn tracks(&self) -> Result<Vec<(Token, String)>, Box<dyn Error>> {
+        self.query(
+            "SELECT track.token, category.name FROM track \
+             JOIN category ON track.category_token = category.token",
+            |row| Ok((token(row, 0)?, row.get(1)?)),
+        )
+    }
+
+    pub fn ego_poses(&self) -> Result<Vec<EgoPose>, Box<dyn Error>> {
+        self.query(
+            "SELECT token, x, y, z, qw, qx, qy, qz, timestamp FROM ego_pose",
+            |row| {
+                Ok(EgoPose {
+                    token: token(row, 0)?,
+                    translation: [row.get(1)?, row.get(2)?, row.get(3)?],
+                    rotation: [row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?],
+                    timestamp: row.get(8)?,
+                })
+            },
+        )
+    }
+
+    pub fn cameras(&self) -> Result<Vec<Camera>, Box<dyn Error>> {
+        self.query(
+            "SELECT token, channel, translation, rotation, intrinsic, width, height FROM camera",
+            |row| {
+                Ok(Camera {
+                    token: token(row, 0)?,
+                    channel: row.get(1)?,
+                    translation: array(row, 2)?,
+                    rotation: array(row, 3)?,
+                    intrinsic: array(row, 4)?,
+                    width: row.get(5)?,
+                    height: row.get(6)?,
+                })
+            },
+        )
+    }
+
+    /// The set of images, ordered by timestamp.
+    ///
+    /// A log without any images (i.e., without an `image` table) results in
+    /// an empty set.
+    pub fn images(&self) -> Result<Vec<Image>, Box<dyn Error>> {
+        let exists: bool = self.connection.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'image')",
+            [],
+            |row| row.get(0),
+        )?;
+
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        self.query(
+            "SELECT camera_token, ego_pose_token, filename_jpg, timestamp FROM image \
+             ORDER BY timestamp",
+            |row| {
+                Ok(Image {
+                    camera: token(row, 0)?,
+                    ego_pose: token(row, 1)?,
+                    filename: row.get(2)?,
+                    timestamp: row.get(3)?,
+                })
+            },
+        )
+    }
+
+    pub fn scenes(&self) -> Result<Vec<Scene>, Box<dyn Error>> {
+        self.query("SELECT token, name FROM scene", |row| {
+            Ok(Scene {
+                token: token(row, 0)?,
+                name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            })
+        })
+    }
+}
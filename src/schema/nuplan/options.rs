@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// The set of options applied to nuPlan logs.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The mapping of cameras (e.g., `CAM_F0`) to stream channels.
+    ///
+    /// If provided, this replaces the default channel table such that any
+    /// camera not listed is skipped.
+    pub channels: Option<HashMap<String, String>>,
+    /// Import each scene of a log as its own stream (rather than the log).
+    pub scenes: bool,
+    /// The directory of the sensor blobs (i.e., the root of the images).
+    pub images: Option<PathBuf>,
+}
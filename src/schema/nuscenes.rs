@@ -6,11 +6,8 @@ use std::io::BufReader;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use nalgebra::{
-    Quaternion, RowSVector as StaticRowVector, SMatrix as StaticMatrix, Translation, UnitQuaternion,
-};
+use nalgebra::{RowSVector as StaticRowVector, SMatrix as StaticMatrix};
 use serde::de::DeserializeOwned;
-use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
 use strem::datastream::frame::sample::detections::bbox::BoundingBox;
 use strem::datastream::frame::sample::detections::{
     Annotation, DetectionRecord, Image, ImageSource,
@@ -20,6 +17,7 @@ use strem::datastream::frame::Frame;
 
 use crate::config::Configuration;
 
+use super::cuboid::Cuboid;
 use super::Schema;
 
 use self::annotation::Annotation as NuAnnotation;
@@ -28,19 +26,21 @@ use self::category::Category as NuCategory;
 use self::data::Data as NuData;
 use self::ego::Ego as NuEgo;
 use self::instance::Instance as NuInstance;
+use self::options::Options as NuOptions;
 use self::sample::Sample as NuSample;
 use self::scene::Scene as NuScene;
 use self::sensor::Sensor as NuSensor;
 
 mod annotation;
-mod calibration;
-mod category;
-mod data;
-mod ego;
+pub(super) mod calibration;
+pub(super) mod category;
+pub(super) mod data;
+pub(super) mod ego;
 mod instance;
+pub(super) mod options;
 mod sample;
 mod scene;
-mod sensor;
+pub(super) mod sensor;
 
 type SampleToken = String;
 type SceneToken = String;
@@ -50,15 +50,73 @@ type EgoToken = String;
 type CalibrationToken = String;
 type SensorToken = String;
 
+/// The variant of a NuScenes-formatted dataset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    NuScenes,
+    Lyft,
+}
+
+impl Variant {
+    /// The name of the variant used to look up its options.
+    fn name(&self) -> &'static str {
+        match self {
+            Variant::NuScenes => "nuscenes",
+            Variant::Lyft => "lyft",
+        }
+    }
+
+    /// The default mapping of sensor channels to stream channels.
+    ///
+    /// The Lyft Level 5 dataset additionally provides a zoomed front camera.
+    pub(super) fn channels(&self) -> HashMap<String, String> {
+        let mut channels = vec![
+            ("CAM_FRONT", "cam::front"),
+            ("CAM_FRONT_LEFT", "cam::front::left"),
+            ("CAM_FRONT_RIGHT", "cam::front::right"),
+            ("CAM_BACK", "cam::back"),
+            ("CAM_BACK_LEFT", "cam::back::left"),
+            ("CAM_BACK_RIGHT", "cam::back::right"),
+        ];
+
+        if *self == Variant::Lyft {
+            channels.push(("CAM_FRONT_ZOOMED", "cam::front::zoomed"));
+        }
+
+        channels
+            .into_iter()
+            .map(|(a, b)| (String::from(a), String::from(b)))
+            .collect()
+    }
+}
+
 pub struct NuScenes<'a> {
     pub root: PathBuf,
     pub config: &'a Configuration,
+    pub variant: Variant,
 }
 
 impl<'a> NuScenes<'a> {
     pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
         let root = root.into();
-        Self { root, config }
+        Self {
+            root,
+            config,
+            variant: Variant::NuScenes,
+        }
+    }
+
+    /// Construct an importer of the Lyft Level 5 dataset.
+    ///
+    /// The Lyft Level 5 dataset uses the NuScenes table layout; therefore,
+    /// only the set of cameras differs, accordingly.
+    pub fn lyft<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self {
+            root,
+            config,
+            variant: Variant::Lyft,
+        }
     }
 
     /// Load JSON-based data from the NuScenes formatted file.
@@ -70,7 +128,7 @@ impl<'a> NuScenes<'a> {
     ///
     /// This will read from a [`BufReader`] and serialize into the appropriate
     /// data structures, accordingly.
-    fn load<T>(&self, filename: &str) -> Result<Vec<T>, Box<dyn Error>>
+    pub(super) fn load<T>(&self, filename: &str) -> Result<Vec<T>, Box<dyn Error>>
     where
         T: DeserializeOwned,
     {
@@ -106,20 +164,7 @@ impl<'a> NuScenes<'a> {
         }
     }
 
-    fn channel(&self, channel: &str) -> Option<String> {
-        match channel {
-            "CAM_FRONT" => Some(String::from("cam::front")),
-            "CAM_FRONT_ZOOMED" => Some(String::from("cam::front::zoomed")),
-            "CAM_FRONT_LEFT" => Some(String::from("cam::front::left")),
-            "CAM_FRONT_RIGHT" => Some(String::from("cam::front::right")),
-            "CAM_BACK" => Some(String::from("cam::back")),
-            "CAM_BACK_LEFT" => Some(String::from("cam::back::left")),
-            "CAM_BACK_RIGHT" => Some(String::from("cam::back::right")),
-            _ => None,
-        }
-    }
-
-    fn image(&self, data: &NuData) -> Option<Image> {
+    pub(super) fn image(&self, data: &NuData) -> Option<Image> {
         if let Some(width) = data.width {
             if let Some(height) = data.height {
                 let source = ImageSource::File(PathBuf::from(&data.filename));
@@ -154,30 +199,15 @@ impl<'a> NuScenes<'a> {
             //
             // This is done to convert a 3D bounding box into a 2D bounding box
             // that can be used by [`strem`], accordingly.
-            let a = self.translate(annotation, ego, calibration);
+            let a = Self::translate(annotation.cuboid(), ego, calibration);
 
             if a.inside(viewport, data.width.unwrap(), data.height.unwrap()) {
-                let m = a.projection(viewport);
-
-                let xmin = m.row(0).iter().copied().fold(f64::NAN, f64::min);
-                let ymin = m.row(1).iter().copied().fold(f64::NAN, f64::min);
-
-                let xmax = m.row(0).iter().copied().fold(f64::NAN, f64::max);
-                let ymax = m.row(1).iter().copied().fold(f64::NAN, f64::max);
-
-                let width = xmax - xmin;
-                let height = ymax - ymin;
-
                 res.entry(label.name.clone())
                     .or_default()
                     .push(Annotation::new(
                         label.name.clone(),
                         1.0,
-                        BoundingBox::AxisAligned(aa::Region::new(
-                            Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
-                            width,
-                            height,
-                        )),
+                        BoundingBox::AxisAligned(a.region(viewport)),
                     ));
             }
         }
@@ -185,49 +215,15 @@ impl<'a> NuScenes<'a> {
         res
     }
 
-    // Translate the [`NuAnnotation`].
+    // Translate the (global) [`Cuboid`] of an annotation.
     //
-    // This includes: (1) translating the [`NuAnnotation`] with respect to the
-    // [`NuEgo`] position, and (2) translating the [`NuAnnotation`] with respect
-    // to the `[NuCalibration]` position.
-    fn translate(
-        &self,
-        annotation: &NuAnnotation,
-        ego: &NuEgo,
-        calibration: &NuCalibration,
-    ) -> NuAnnotation {
-        annotation
-            .clone()
-            .transform(
-                Translation::<f64, 3>::new(
-                    ego.translation[0],
-                    ego.translation[1],
-                    ego.translation[2],
-                )
-                .inverse(),
-                UnitQuaternion::from_quaternion(Quaternion::new(
-                    ego.rotation[0],
-                    ego.rotation[1],
-                    ego.rotation[2],
-                    ego.rotation[3],
-                ))
-                .inverse(),
-            )
-            .transform(
-                Translation::<f64, 3>::new(
-                    calibration.translation[0],
-                    calibration.translation[1],
-                    calibration.translation[2],
-                )
-                .inverse(),
-                UnitQuaternion::from_quaternion(Quaternion::new(
-                    calibration.rotation[0],
-                    calibration.rotation[1],
-                    calibration.rotation[2],
-                    calibration.rotation[3],
-                ))
-                .inverse(),
-            )
+    // This includes: (1) translating the [`Cuboid`] with respect to the
+    // [`NuEgo`] position, and (2) translating the [`Cuboid`] with respect to
+    // the `[NuCalibration]` position.
+    pub(super) fn translate(cuboid: Cuboid, ego: &NuEgo, calibration: &NuCalibration) -> Cuboid {
+        cuboid
+            .into_frame(ego.translation, ego.rotation)
+            .into_frame(calibration.translation, calibration.rotation)
     }
 }
 
@@ -235,6 +231,12 @@ impl Schema for NuScenes<'_> {
     fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
         self.debug(&format!("root directory at `{}`", self.root.display()));
 
+        let options: NuOptions = self.config.options(self.variant.name())?;
+        let channels = options
+            .channels
+            .clone()
+            .unwrap_or_else(|| self.variant.channels());
+
         // Set up internal database.
         //
         // Because NuScenes uses a foreign key-based system, the keys and
@@ -243,13 +245,13 @@ impl Schema for NuScenes<'_> {
         self.debug("building internal database");
 
         let scenes: HashMap<SceneToken, NuScene> = self
-            .load::<NuScene>("scene.json")?
+            .load::<NuScene>(&options.table("scene"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
 
         let samples: HashMap<SampleToken, NuSample> = self
-            .load::<NuSample>("sample.json")?
+            .load::<NuSample>(&options.table("sample"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
@@ -260,7 +262,7 @@ impl Schema for NuScenes<'_> {
         // [`NuAnnotation`] must be created.
         let mut annotations: HashMap<SampleToken, Vec<NuAnnotation>> = HashMap::new();
 
-        for a in self.load::<NuAnnotation>("sample_annotation.json")? {
+        for a in self.load::<NuAnnotation>(&options.table("sample_annotation"))? {
             let token = a.sample_token.clone();
             annotations.entry(token).or_default().push(a);
         }
@@ -271,37 +273,37 @@ impl Schema for NuScenes<'_> {
         // [`NuData`] must be created.
         let mut datas: HashMap<SampleToken, Vec<NuData>> = HashMap::new();
 
-        for d in self.load::<NuData>("sample_data.json")? {
+        for d in self.load::<NuData>(&options.table("sample_data"))? {
             let token = d.sample_token.clone();
             datas.entry(token).or_default().push(d);
         }
 
         let instances: HashMap<InstanceToken, NuInstance> = self
-            .load::<NuInstance>("instance.json")?
+            .load::<NuInstance>(&options.table("instance"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
 
         let categories: HashMap<CategoryToken, NuCategory> = self
-            .load::<NuCategory>("category.json")?
+            .load::<NuCategory>(&options.table("category"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
 
         let egos: HashMap<EgoToken, NuEgo> = self
-            .load::<NuEgo>("ego_pose.json")?
+            .load::<NuEgo>(&options.table("ego_pose"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
 
         let calibrations: HashMap<CalibrationToken, NuCalibration> = self
-            .load::<NuCalibration>("calibrated_sensor.json")?
+            .load::<NuCalibration>(&options.table("calibrated_sensor"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
 
         let sensors: HashMap<SensorToken, NuSensor> = self
-            .load::<NuSensor>("sensor.json")?
+            .load::<NuSensor>(&options.table("sensor"))?
             .into_iter()
             .map(|x| (x.token.clone(), x))
             .collect();
@@ -331,10 +333,10 @@ impl Schema for NuScenes<'_> {
 
                     // If [`Some`] mapping exists, proceed.
                     //
-                    // The [`self::channel`] function is used to filter out
-                    // sensor/data that we do not want to consider.
-                    if let Some(channel) = self.channel(&sensor.channel) {
-                        let mut record = DetectionRecord::new(channel, self.image(data));
+                    // The channel table is used to filter out sensor/data that
+                    // we do not want to consider.
+                    if let Some(channel) = channels.get(&sensor.channel) {
+                        let mut record = DetectionRecord::new(channel.clone(), self.image(data));
 
                         // Add the set of annotations to the [`DetectionRecord`].
                         //
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The set of options applied to NuScenes-formatted datasets.
+///
+/// This allows datasets that share the NuScenes table layout (e.g., Lyft
+/// Level 5) but differ in their camera set or file names to be imported.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The mapping of sensor channels (e.g., `CAM_FRONT`) to stream channels.
+    ///
+    /// If provided, this replaces the default channel table of the dataset
+    /// such that any sensor not listed is skipped.
+    pub channels: Option<HashMap<String, String>>,
+    /// The mapping of table names (e.g., `ego_pose`) to file names.
+    pub tables: HashMap<String, String>,
+}
+
+impl Options {
+    /// The name of the file of a table.
+    ///
+    /// By default, this is the table name with a `.json` extension.
+    pub fn table(&self, name: &str) -> String {
+        self.tables
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| format!("{}.json", name))
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::csv::table::{self, Table};
+use super::Schema;
+
+use self::label::{Label as OiLabel, Layout as OiLayout};
+use self::options::Options as OiOptions;
+
+mod label;
+mod options;
+
+type ImageId = String;
+type LabelName = String;
+
+/// The suffix of the box annotations.
+const SUFFIX: &str = "-annotations-bbox.csv";
+
+/// The Open Images (V6/V7) bounding boxes.
+///
+/// The root is the directory containing the `*-annotations-bbox.csv` files
+/// and the `*class-descriptions*.csv` file. Each annotation file forms a
+/// stream of its images, ordered by `ImageID`.
+pub struct OpenImages<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> OpenImages<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", OpenImagesDebug::from(msg));
+        }
+    }
+
+    /// Read a (small) CSV file into a [`Table`].
+    fn table(&self, path: &Path, header: bool) -> Result<Table, Box<dyn Error>> {
+        let data = fs::read_to_string(path).or(Err(Box::new(OpenImagesError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        Ok(Table::parse(&data, ',', header))
+    }
+
+    /// Find the set of annotation files.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+
+            if path.to_string_lossy().ends_with(SUFFIX) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Load the mapping of class identifiers to display names.
+    ///
+    /// The V6 descriptions do not provide a header whereas the V7 descriptions
+    /// do (i.e., `LabelName,DisplayName`); therefore, the header is skipped as
+    /// a regular row. The `boxable` descriptions are preferred, if found.
+    fn classes(&self) -> Result<HashMap<LabelName, String>, Box<dyn Error>> {
+        let mut paths = fs::read_dir(&self.root)?
+            .map(|x| x.map(|x| x.path()))
+            .collect::<Result<Vec<PathBuf>, _>>()?
+            .into_iter()
+            .filter(|x| {
+                let name = x.file_name().unwrap().to_string_lossy();
+                name.contains("class-descriptions") && name.ends_with(".csv")
+            })
+            .collect::<Vec<PathBuf>>();
+
+        paths.sort_by_key(|x| !x.to_string_lossy().contains("boxable"));
+
+        let path = match paths.first() {
+            Some(path) => path,
+            None => {
+                self.debug("no class descriptions found");
+                return Ok(HashMap::new());
+            }
+        };
+
+        let mut classes = HashMap::new();
+
+        for (_, fields) in self.table(path, false)?.rows {
+            if let [id, name, ..] = &fields[..] {
+                if id != "LabelName" {
+                    classes.insert(id.clone(), name.clone());
+                }
+            }
+        }
+
+        self.debug(&format!(
+            "read {} classes from `{}`",
+            classes.len(),
+            path.display()
+        ));
+
+        Ok(classes)
+    }
+
+    /// Load the image dimensions from the sizes file, if provided.
+    fn sizes(&self, options: &OiOptions) -> Result<HashMap<ImageId, (u32, u32)>, Box<dyn Error>> {
+        let path = match &options.sizes {
+            Some(path) => path,
+            None => return Ok(HashMap::new()),
+        };
+
+        let table = self.table(path, true)?;
+
+        let (id, width, height) = match (
+            table.column("ImageID"),
+            table.column("Width"),
+            table.column("Height"),
+        ) {
+            (Some(id), Some(width), Some(height)) => (id, width, height),
+            _ => {
+                return Err(Box::new(OpenImagesError::from(format!(
+                    "{}: expected `ImageID`, `Width`, and `Height` columns",
+                    path.display()
+                ))))
+            }
+        };
+
+        let mut sizes = HashMap::new();
+
+        for (line, fields) in table.rows.iter() {
+            let size = |i: usize| {
+                fields
+                    .get(i)
+                    .and_then(|x| x.parse::<f64>().ok())
+                    .map(|x| x as u32)
+                    .ok_or_else(|| {
+                        OpenImagesError::from(format!(
+                            "{}:{}: invalid image dimensions",
+                            path.display(),
+                            line
+                        ))
+                    })
+            };
+
+            if let Some(image) = fields.get(id) {
+                sizes.insert(image.clone(), (size(width)?, size(height)?));
+            }
+        }
+
+        Ok(sizes)
+    }
+
+    /// Load the set of [`OiLabel`] of an annotation file, grouped by image.
+    ///
+    /// The annotation files may be large (e.g., the train split); therefore,
+    /// the file is read line by line and only the kept boxes are collected.
+    fn load(
+        &self,
+        path: &Path,
+        options: &OiOptions,
+    ) -> Result<BTreeMap<ImageId, Vec<OiLabel>>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(OpenImagesError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut lines = BufReader::new(infile).lines();
+
+        let header = table::fields(&lines.next().transpose()?.unwrap_or_default(), ',');
+        let layout = OiLayout::new(&header)
+            .map_err(|e| OpenImagesError::from(format!("{}: {}", path.display(), e)))?;
+
+        let mut labels: BTreeMap<ImageId, Vec<OiLabel>> = BTreeMap::new();
+
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let label = OiLabel::parse(&table::fields(&line, ','), &layout).map_err(|e| {
+                OpenImagesError::from(format!("{}:{}: {}", path.display(), i + 2, e))
+            })?;
+
+            // The images without a kept box are still part of the stream.
+            let entry = labels.entry(label.image.clone()).or_default();
+
+            if options.keep(&label) {
+                entry.push(label);
+            }
+        }
+
+        self.debug(&format!("read labels from `{}`", path.display()));
+
+        Ok(labels)
+    }
+
+    /// Find the dimensions of an image.
+    ///
+    /// The dimensions are taken from the sizes file and, if absent, read from
+    /// the image itself.
+    fn dimensions(
+        &self,
+        path: &Path,
+        id: &str,
+        sizes: &HashMap<ImageId, (u32, u32)>,
+    ) -> Result<(u32, u32), Box<dyn Error>> {
+        if let Some(size) = sizes.get(id) {
+            return Ok(*size);
+        }
+
+        let size = imagesize::size(path).map_err(|e| {
+            OpenImagesError::from(format!("unable to read `{}`: {}", path.display(), e))
+        })?;
+
+        Ok((size.width as u32, size.height as u32))
+    }
+
+    /// Convert the normalized boxes into pixel boxes.
+    fn annotations(
+        &self,
+        labels: &[OiLabel],
+        width: f64,
+        height: f64,
+        classes: &HashMap<LabelName, String>,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for label in labels.iter() {
+            let [xmin, xmax, ymin, ymax] = label.bbox;
+
+            let name = classes
+                .get(&label.label)
+                .cloned()
+                .unwrap_or_else(|| label.label.clone());
+
+            let w = (xmax - xmin) * width;
+            let h = (ymax - ymin) * height;
+
+            res.entry(name.clone()).or_default().push(Annotation::new(
+                name,
+                label.confidence,
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(xmin * width + (w / 2.0), ymin * height + (h / 2.0)),
+                    w,
+                    h,
+                )),
+            ));
+        }
+
+        res
+    }
+}
+
+impl Schema for OpenImages<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: OiOptions = self.config.options("openimages")?;
+
+        let classes = self.classes()?;
+        let sizes = self.sizes(&options)?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let filename = path.file_name().unwrap().to_string_lossy().into_owned();
+            let name = filename.trim_end_matches(SUFFIX).to_string();
+
+            // Find the directory of the images.
+            //
+            // The images of a split are found in a directory named after the
+            // split, without the release prefix (e.g., `oidv6-train` is found
+            // at `train`), unless a directory is provided.
+            let dir = match &options.images {
+                Some(dir) => dir.clone(),
+                None => {
+                    let split = match name.split_once('-') {
+                        Some((prefix, split)) if prefix.starts_with("oidv") => split,
+                        _ => &name,
+                    };
+
+                    self.root.join(split)
+                }
+            };
+
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each image of the annotation file and
+            // collect its boxes into a linear stream, ordered by `ImageID`.
+            let mut frames = Vec::new();
+
+            for (index, (id, labels)) in self.load(&path, &options)?.iter().enumerate() {
+                let path = dir.join(format!("{}.jpg", id));
+                let (width, height) = self.dimensions(&path, id, &sizes)?;
+
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(
+                    String::from("cam"),
+                    Some(Image::new(ImageSource::File(path), width, height)),
+                );
+
+                record.annotations =
+                    self.annotations(labels, width as f64, height as f64, &classes);
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenImagesDebug {
+    msg: String,
+}
+
+impl From<&str> for OpenImagesDebug {
+    fn from(msg: &str) -> Self {
+        OpenImagesDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for OpenImagesDebug {
+    fn from(msg: String) -> Self {
+        OpenImagesDebug { msg }
+    }
+}
+
+impl fmt::Display for OpenImagesDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: openimages: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenImagesError {
+    msg: String,
+}
+
+impl From<&str> for OpenImagesError {
+    fn from(msg: &str) -> Self {
+        OpenImagesError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for OpenImagesError {
+    fn from(msg: String) -> Self {
+        OpenImagesError { msg }
+    }
+}
+
+impl fmt::Display for OpenImagesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "openimages: {}", self.msg)
+    }
+}
+
+impl Error for OpenImagesError {}
@@ -0,0 +1,84 @@
+use std::error::Error;
+
+/// The positions of the columns of an `*-annotations-bbox.csv` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    image: usize,
+    label: usize,
+    confidence: Option<usize>,
+    /// XMin, XMax, YMin, YMax
+    bbox: [usize; 4],
+    occluded: Option<usize>,
+    truncated: Option<usize>,
+    group: Option<usize>,
+}
+
+impl Layout {
+    /// Resolve the set of columns from the header.
+    pub fn new(header: &[String]) -> Result<Self, Box<dyn Error>> {
+        let optional = |name: &str| header.iter().position(|x| x == name);
+        let required =
+            |name: &str| optional(name).ok_or_else(|| format!("missing column `{}`", name));
+
+        Ok(Layout {
+            image: required("ImageID")?,
+            label: required("LabelName")?,
+            confidence: optional("Confidence"),
+            bbox: [
+                required("XMin")?,
+                required("XMax")?,
+                required("YMin")?,
+                required("YMax")?,
+            ],
+            occluded: optional("IsOccluded"),
+            truncated: optional("IsTruncated"),
+            group: optional("IsGroupOf"),
+        })
+    }
+}
+
+/// A single box of an `*-annotations-bbox.csv` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub image: String,
+    /// The machine identifier of the class (e.g., `/m/0k4j`).
+    pub label: String,
+    pub confidence: f64,
+    /// xmin, xmax, ymin, ymax (normalized)
+    pub bbox: [f64; 4],
+    pub occluded: bool,
+    pub truncated: bool,
+    pub group: bool,
+}
+
+impl Label {
+    pub fn parse(fields: &[String], layout: &Layout) -> Result<Self, Box<dyn Error>> {
+        let field = |i: usize| {
+            fields
+                .get(i)
+                .map(|x| x.as_str())
+                .ok_or_else(|| format!("missing field {}", i))
+        };
+
+        let flag = |i: Option<usize>| i.and_then(|i| fields.get(i)).map(|x| x == "1");
+
+        let mut bbox = [0.0; 4];
+
+        for (value, i) in bbox.iter_mut().zip(layout.bbox) {
+            *value = field(i)?.parse()?;
+        }
+
+        Ok(Label {
+            image: field(layout.image)?.to_string(),
+            label: field(layout.label)?.to_string(),
+            confidence: match layout.confidence {
+                Some(i) => field(i)?.parse()?,
+                None => 1.0,
+            },
+            bbox,
+            occluded: flag(layout.occluded).unwrap_or(false),
+            truncated: flag(layout.truncated).unwrap_or(false),
+            group: flag(layout.group).unwrap_or(false),
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use super::label::Label;
+
+/// The set of options applied to Open Images boxes.
+///
+/// By default, every box is kept and the images are found next to the
+/// annotations (e.g., `validation/<ImageID>.jpg`).
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// Keep boxes marked as `IsGroupOf`.
+    pub group: bool,
+    /// Keep boxes marked as `IsOccluded`.
+    pub occluded: bool,
+    /// Keep boxes marked as `IsTruncated`.
+    pub truncated: bool,
+    /// The directory of the images.
+    pub images: Option<PathBuf>,
+    /// The CSV file of the image dimensions (i.e., `ImageID`, `Width`, and
+    /// `Height` columns).
+    ///
+    /// If not provided (or an image is not listed), then the dimensions are
+    /// read from the image itself.
+    pub sizes: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            group: true,
+            occluded: true,
+            truncated: true,
+            images: None,
+            sizes: None,
+        }
+    }
+}
+
+impl Options {
+    /// Check if the [`Label`] passes the set of filters.
+    ///
+    /// A flag of `-1` (i.e., unknown) does not mark the box.
+    pub fn keep(&self, label: &Label) -> bool {
+        if label.group && !self.group {
+            return false;
+        }
+
+        if label.occluded && !self.occluded {
+            return false;
+        }
+
+        if label.truncated && !self.truncated {
+            return false;
+        }
+
+        true
+    }
+}
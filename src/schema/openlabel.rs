@@ -0,0 +1,525 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::document::{
+    Document as OlDocument, FrameData as OlFrameData, Geometry as OlGeometry,
+    Interval as OlInterval, OpenLabel as OlOpenLabel,
+};
+use self::options::Options as OlOptions;
+
+mod document;
+mod options;
+
+type FrameId = i64;
+type StreamName = String;
+
+/// The stream of a file without any camera streams.
+const DEFAULT: &str = "cam";
+
+/// The ASAM OpenLABEL (JSON) format.
+///
+/// The input may either be a single file or a directory of files where each
+/// file forms a stream. Each camera stream of the file maps to a channel (or,
+/// if none are declared, a single `cam` channel) and the `bbox` and `rbbox`
+/// geometries of each object are imported. The static (i.e.,
+/// frame-independent) object data is not considered.
+pub struct OpenLabel<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> OpenLabel<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", OpenLabelDebug::from(msg));
+        }
+    }
+
+    /// Load the [`OlOpenLabel`] of a file.
+    fn load(&self, path: &Path) -> Result<OlOpenLabel, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(OpenLabelError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+        let data: OlDocument = serde_json::from_reader(reader)?;
+
+        self.debug(&format!(
+            "serde: deserialized data from `{}`",
+            path.display()
+        ));
+
+        Ok(data.openlabel)
+    }
+
+    /// The set of files to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "json").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Validate the metadata and coordinate systems of a file.
+    ///
+    /// Only the 1.x versions of the schema are supported. Each coordinate
+    /// system must have a declared parent (if any) and the coordinate system
+    /// of a camera stream must be a `sensor_cs`.
+    fn validate(&self, data: &OlOpenLabel) -> Result<(), String> {
+        if let Some(version) = &data.metadata.schema_version {
+            if !version.starts_with("1.") {
+                return Err(format!("unsupported schema version `{}`", version));
+            }
+        }
+
+        for (name, cs) in data.coordinate_systems.iter() {
+            if !cs.parent.is_empty() && !data.coordinate_systems.contains_key(&cs.parent) {
+                return Err(format!(
+                    "coordinate system `{}`: undeclared parent `{}`",
+                    name, cs.parent
+                ));
+            }
+
+            let camera = data
+                .streams
+                .get(name)
+                .is_some_and(|x| x.kind.as_deref() == Some("camera"));
+
+            if camera && cs.kind != "sensor_cs" {
+                return Err(format!(
+                    "coordinate system `{}`: expected `sensor_cs` for a camera stream, found `{}`",
+                    name, cs.kind
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate and collect the set of frames, keyed by frame number.
+    ///
+    /// Each frame must be an integer within the frame intervals (if any) of
+    /// the file. Likewise, each object of a frame must be declared and the
+    /// frame must be within the frame intervals (if any) of the object.
+    fn frames<'b>(
+        &self,
+        data: &'b OlOpenLabel,
+    ) -> Result<BTreeMap<FrameId, &'b OlFrameData>, String> {
+        intervals(&data.frame_intervals)?;
+
+        let mut res = BTreeMap::new();
+
+        for (key, frame) in data.frames.iter() {
+            let id = key
+                .parse::<FrameId>()
+                .map_err(|_| format!("frame `{}`: expected an integer frame number", key))?;
+
+            if !data.frame_intervals.is_empty()
+                && !data.frame_intervals.iter().any(|x| x.contains(id))
+            {
+                return Err(format!("frame `{}`: outside of the frame intervals", id));
+            }
+
+            for uid in frame.objects.keys() {
+                let object = data
+                    .objects
+                    .get(uid)
+                    .ok_or_else(|| format!("frame `{}`: undeclared object `{}`", id, uid))?;
+
+                intervals(&object.frame_intervals)
+                    .map_err(|e| format!("object `{}`: {}", uid, e))?;
+
+                if !object.frame_intervals.is_empty()
+                    && !object.frame_intervals.iter().any(|x| x.contains(id))
+                {
+                    return Err(format!(
+                        "frame `{}`: object `{}` outside of its frame intervals",
+                        id, uid
+                    ));
+                }
+            }
+
+            res.insert(id, frame);
+        }
+
+        Ok(res)
+    }
+
+    /// Resolve the camera stream of a geometry.
+    ///
+    /// The geometry references its stream either directly or through its
+    /// coordinate system, which must be declared (if any are declared) and
+    /// be associated with a camera stream. If no reference is provided, then
+    /// the file must contain a single camera stream. If the file contains no
+    /// camera streams, then the default stream is used.
+    fn stream(
+        &self,
+        data: &OlOpenLabel,
+        cameras: &[StreamName],
+        geometry: &OlGeometry,
+    ) -> Result<StreamName, String> {
+        if let Some(cs) = &geometry.coordinate_system {
+            if !data.coordinate_systems.is_empty() && !data.coordinate_systems.contains_key(cs) {
+                return Err(format!("undeclared coordinate system `{}`", cs));
+            }
+        }
+
+        if cameras.is_empty() {
+            return Ok(String::from(DEFAULT));
+        }
+
+        match geometry
+            .stream
+            .as_ref()
+            .or(geometry.coordinate_system.as_ref())
+        {
+            Some(name) => cameras.iter().find(|x| *x == name).cloned().ok_or_else(|| {
+                format!(
+                    "coordinate system `{}` is not associated with a camera stream",
+                    name
+                )
+            }),
+            None => match cameras {
+                [camera] => Ok(camera.clone()),
+                _ => Err(format!(
+                    "missing coordinate system (ambiguous between {} camera streams)",
+                    cameras.len()
+                )),
+            },
+        }
+    }
+
+    /// Reference the image of a camera stream at a frame, if any.
+    ///
+    /// The frame-specific properties of the stream take precedence over the
+    /// static properties of the stream.
+    fn image(
+        &self,
+        data: &OlOpenLabel,
+        frame: Option<&OlFrameData>,
+        stream: &str,
+    ) -> Option<Image> {
+        let dynamic = frame.and_then(|x| x.frame_properties.streams.get(stream));
+        let fixed = data.streams.get(stream);
+
+        let uri = dynamic
+            .and_then(|x| x.uri.as_ref())
+            .or(fixed.and_then(|x| x.uri.as_ref()))?;
+
+        let intrinsics = dynamic
+            .and_then(|x| x.stream_properties.intrinsics_pinhole.as_ref())
+            .or(fixed.and_then(|x| x.stream_properties.intrinsics_pinhole.as_ref()));
+
+        let path = PathBuf::from(uri);
+
+        let (width, height) = match intrinsics {
+            Some(x) => (x.width_px, x.height_px),
+            None => {
+                let size = imagesize::size(&path).ok()?;
+                (size.width as u32, size.height as u32)
+            }
+        };
+
+        Some(Image::new(ImageSource::File(path), width, height))
+    }
+
+    /// Convert a `bbox` or `rbbox` into an axis-aligned region.
+    ///
+    /// The rotated boxes are converted into the tightest axis-aligned box
+    /// about the rotated box, accordingly.
+    fn region(&self, geometry: &OlGeometry, rotated: bool) -> Result<aa::Region, String> {
+        let (x, y, width, height) = match (&geometry.val[..], rotated) {
+            ([x, y, width, height], false) => (*x, *y, *width, *height),
+            ([x, y, width, height, alpha], true) => {
+                let (sin, cos) = alpha.sin_cos();
+
+                (
+                    *x,
+                    *y,
+                    (width * cos).abs() + (height * sin).abs(),
+                    (width * sin).abs() + (height * cos).abs(),
+                )
+            }
+            (val, _) => {
+                return Err(format!(
+                    "expected {} values for `{}`, found {}",
+                    if rotated { 5 } else { 4 },
+                    if rotated { "rbbox" } else { "bbox" },
+                    val.len()
+                ))
+            }
+        };
+
+        Ok(aa::Region::new(Point::new(x, y), width, height))
+    }
+
+    /// Collect the set of annotations of a frame, grouped by camera stream.
+    fn annotations(
+        &self,
+        data: &OlOpenLabel,
+        cameras: &[StreamName],
+        frame: &OlFrameData,
+    ) -> Result<HashMap<StreamName, HashMap<String, Vec<Annotation>>>, String> {
+        let mut res: HashMap<StreamName, HashMap<String, Vec<Annotation>>> = HashMap::new();
+
+        for (uid, object) in frame.objects.iter() {
+            // The object has already been validated as declared.
+            let label = &data.objects[uid].kind;
+
+            let geometries = object
+                .object_data
+                .bbox
+                .iter()
+                .map(|x| (x, false))
+                .chain(object.object_data.rbbox.iter().map(|x| (x, true)));
+
+            for (geometry, rotated) in geometries {
+                let stream = self
+                    .stream(data, cameras, geometry)
+                    .map_err(|e| format!("object `{}`: {}", uid, e))?;
+
+                let region = self
+                    .region(geometry, rotated)
+                    .map_err(|e| format!("object `{}`: {}", uid, e))?;
+
+                res.entry(stream)
+                    .or_default()
+                    .entry(label.clone())
+                    .or_default()
+                    .push(Annotation::new(
+                        label.clone(),
+                        1.0,
+                        BoundingBox::AxisAligned(region),
+                    ));
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Merge a set of frame intervals into a sorted set of disjoint intervals.
+fn merge(intervals: &[OlInterval]) -> Vec<(FrameId, FrameId)> {
+    let mut intervals = intervals
+        .iter()
+        .map(|x| (x.frame_start, x.frame_end))
+        .collect::<Vec<(FrameId, FrameId)>>();
+
+    intervals.sort();
+
+    let mut res: Vec<(FrameId, FrameId)> = Vec::new();
+
+    for (start, end) in intervals {
+        match res.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => res.push((start, end)),
+        }
+    }
+
+    res
+}
+
+/// Validate a set of frame intervals.
+///
+/// Each interval must start before (or at) its end.
+fn intervals(intervals: &[OlInterval]) -> Result<(), String> {
+    for interval in intervals {
+        if interval.frame_start > interval.frame_end {
+            return Err(format!(
+                "invalid frame interval [{}, {}]",
+                interval.frame_start, interval.frame_end
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+impl Schema for OpenLabel<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: OlOptions = self.config.options("openlabel")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let data = self.load(&path)?;
+
+            let context = |e: String| OpenLabelError::from(format!("{}: {}", path.display(), e));
+
+            let cameras = data
+                .streams
+                .iter()
+                .filter(|(_, x)| x.kind.as_deref() == Some("camera"))
+                .map(|(x, _)| x.clone())
+                .collect::<Vec<StreamName>>();
+
+            let streams = if cameras.is_empty() {
+                self.debug(&format!("{}: no camera streams, using `{}`", name, DEFAULT));
+                vec![String::from(DEFAULT)]
+            } else {
+                cameras.clone()
+            };
+
+            self.validate(&data).map_err(context)?;
+            let frames = self.frames(&data).map_err(context)?;
+
+            // Determine the set of frames of the file.
+            //
+            // The frame intervals take precedence such that the gaps between
+            // disjoint intervals are skipped; otherwise, the range of frames is
+            // determined from the set of frames, accordingly.
+            let ranges = if data.frame_intervals.is_empty() {
+                frames
+                    .keys()
+                    .next()
+                    .zip(frames.keys().next_back())
+                    .map(|(a, b)| (*a, *b))
+                    .into_iter()
+                    .collect()
+            } else {
+                merge(&data.frame_intervals)
+            };
+
+            // Construct the set of [`Frame`].
+            //
+            // Each frame of the intervals is inserted such that no gaps exist
+            // with a record per camera stream.
+            let mut res = Vec::new();
+
+            let ids = ranges.into_iter().flat_map(|(start, end)| start..=end);
+
+            for (index, id) in ids.enumerate() {
+                let mut frame = Frame::new(index);
+                let data_frame = frames.get(&id).copied();
+
+                let mut annotations = match data_frame {
+                    Some(x) => self
+                        .annotations(&data, &cameras, x)
+                        .map_err(|e| context(format!("frame `{}`: {}", id, e)))?,
+                    None => HashMap::new(),
+                };
+
+                for camera in streams.iter() {
+                    let mut record = DetectionRecord::new(
+                        options.channel(camera),
+                        self.image(&data, data_frame, camera),
+                    );
+
+                    if let Some(annotations) = annotations.remove(camera) {
+                        record.annotations = annotations;
+                    }
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                res.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, res));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenLabelDebug {
+    msg: String,
+}
+
+impl From<&str> for OpenLabelDebug {
+    fn from(msg: &str) -> Self {
+        OpenLabelDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for OpenLabelDebug {
+    fn from(msg: String) -> Self {
+        OpenLabelDebug { msg }
+    }
+}
+
+impl fmt::Display for OpenLabelDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: openlabel: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenLabelError {
+    msg: String,
+}
+
+impl From<&str> for OpenLabelError {
+    fn from(msg: &str) -> Self {
+        OpenLabelError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for OpenLabelError {
+    fn from(msg: String) -> Self {
+        OpenLabelError { msg }
+    }
+}
+
+impl fmt::Display for OpenLabelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "openlabel: {}", self.msg)
+    }
+}
+
+impl Error for OpenLabelError {}
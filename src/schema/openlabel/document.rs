@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// The root of an OpenLABEL file.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Document {
+    pub openlabel: OpenLabel,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct OpenLabel {
+    #[serde(default)]
+    pub metadata: Metadata,
+    #[serde(default)]
+    pub streams: BTreeMap<String, Stream>,
+    #[serde(default)]
+    pub coordinate_systems: BTreeMap<String, CoordinateSystem>,
+    #[serde(default)]
+    pub frame_intervals: Vec<Interval>,
+    #[serde(default)]
+    pub objects: BTreeMap<String, Object>,
+    /// The frames keyed by their (integer) frame number.
+    #[serde(default)]
+    pub frames: BTreeMap<String, FrameData>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct Metadata {
+    pub schema_version: Option<String>,
+}
+
+/// A stream (i.e., sensor) or its frame-specific properties.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct Stream {
+    /// camera, lidar, radar, gps_imu, other
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub stream_properties: StreamProperties,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct StreamProperties {
+    pub intrinsics_pinhole: Option<Intrinsics>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Intrinsics {
+    pub width_px: u32,
+    pub height_px: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CoordinateSystem {
+    /// scene_cs, local_cs, geo_utm, geo_wgs84, sensor_cs, custom_cs
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub parent: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct Interval {
+    pub frame_start: i64,
+    pub frame_end: i64,
+}
+
+impl Interval {
+    /// Check if the frame is within the (inclusive) interval.
+    pub fn contains(&self, frame: i64) -> bool {
+        self.frame_start <= frame && frame <= self.frame_end
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Object {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub frame_intervals: Vec<Interval>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct FrameData {
+    #[serde(default)]
+    pub frame_properties: FrameProperties,
+    #[serde(default)]
+    pub objects: BTreeMap<String, FrameObject>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct FrameProperties {
+    #[serde(default)]
+    pub streams: BTreeMap<String, Stream>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct FrameObject {
+    #[serde(default)]
+    pub object_data: ObjectData,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct ObjectData {
+    #[serde(default)]
+    pub bbox: Vec<Geometry>,
+    #[serde(default)]
+    pub rbbox: Vec<Geometry>,
+}
+
+/// A `bbox` or `rbbox` of an object.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Geometry {
+    /// bbox: x, y, width, height (from the center)
+    /// rbbox: x, y, width, height, alpha (from the center)
+    pub val: Vec<f64>,
+    pub coordinate_system: Option<String>,
+    pub stream: Option<String>,
+}
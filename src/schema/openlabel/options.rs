@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The set of options applied to OpenLABEL files.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The mapping of camera streams (e.g., `CAM_FRONT`) to channels.
+    pub channels: HashMap<String, String>,
+}
+
+impl Options {
+    /// The channel of a camera stream.
+    ///
+    /// By default, the stream name is lowercased and each `_` is replaced
+    /// by `::` (e.g., `CAM_FRONT` becomes `cam::front`).
+    pub fn channel(&self, stream: &str) -> String {
+        self.channels
+            .get(stream)
+            .cloned()
+            .unwrap_or_else(|| stream.to_lowercase().replace('_', "::"))
+    }
+}
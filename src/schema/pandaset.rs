@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flate2::read::GzDecoder;
+use nalgebra::SMatrix as StaticMatrix;
+use serde::de::DeserializeOwned;
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::cuboid::Cuboid;
+use super::Schema;
+
+use self::camera::{Intrinsics as PandaIntrinsics, Pose as PandaPose};
+use self::dataframe::DataFrame;
+
+mod camera;
+mod dataframe;
+mod pickle;
+
+/// The dimensions of every PandaSet image.
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+
+/// A camera of a sequence (i.e., its channel, intrinsics, and poses).
+struct PandaCamera {
+    name: String,
+    channel: String,
+    view: StaticMatrix<f64, 3, 3>,
+    poses: Vec<PandaPose>,
+}
+
+/// The PandaSet dataset.
+///
+/// The root may either be a single sequence (e.g., `001`) or a directory of
+/// sequences. Each sequence forms a stream where each of its frames forms a
+/// [`Frame`] and the cuboids of the frame are projected onto each camera.
+pub struct PandaSet<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> PandaSet<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", PandaSetDebug::from(msg));
+        }
+    }
+
+    fn channel(&self, camera: &str) -> Option<String> {
+        match camera {
+            "front_camera" => Some(String::from("cam::front")),
+            "front_left_camera" => Some(String::from("cam::front::left")),
+            "front_right_camera" => Some(String::from("cam::front::right")),
+            "left_camera" => Some(String::from("cam::side::left")),
+            "right_camera" => Some(String::from("cam::side::right")),
+            "back_camera" => Some(String::from("cam::back")),
+            _ => None,
+        }
+    }
+
+    /// Collect the set of sequence directories.
+    fn sequences(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if self.root.join("camera").is_dir() {
+            return Ok(vec![self.root.clone()]);
+        }
+
+        let mut sequences = Vec::new();
+
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+
+            if path.join("camera").is_dir() {
+                sequences.push(path);
+            }
+        }
+
+        sequences.sort();
+
+        Ok(sequences)
+    }
+
+    /// Load a JSON file.
+    fn load<T>(&self, path: &Path) -> Result<T, Box<dyn Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let infile = File::open(path).or(Err(Box::new(PandaSetError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let data = serde_json::from_reader(BufReader::new(infile))
+            .map_err(|e| PandaSetError::from(format!("{}: {}", path.display(), e)))?;
+
+        Ok(data)
+    }
+
+    /// Load the intrinsics and poses of each camera of a sequence.
+    fn cameras(&self, sequence: &Path) -> Result<Vec<PandaCamera>, Box<dyn Error>> {
+        let mut cameras = Vec::new();
+
+        for entry in fs::read_dir(sequence.join("camera"))? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+            if let Some(channel) = self.channel(&name) {
+                let intrinsics: PandaIntrinsics = self.load(&path.join("intrinsics.json"))?;
+                let poses: Vec<PandaPose> = self.load(&path.join("poses.json"))?;
+
+                let view = StaticMatrix::<f64, 3, 3>::new(
+                    intrinsics.fx,
+                    0.0,
+                    intrinsics.cx,
+                    0.0,
+                    intrinsics.fy,
+                    intrinsics.cy,
+                    0.0,
+                    0.0,
+                    1.0,
+                );
+
+                cameras.push(PandaCamera {
+                    name,
+                    channel,
+                    view,
+                    poses,
+                });
+            }
+        }
+
+        cameras.sort_by(|a, b| a.channel.cmp(&b.channel));
+
+        Ok(cameras)
+    }
+
+    /// Load the set of cuboids of a frame (i.e., a gzipped pickle of a pandas
+    /// `DataFrame`).
+    ///
+    /// The cuboids in the overlap of the two lidars are annotated once per
+    /// lidar; therefore, the duplicates of the front-facing lidar (i.e., a
+    /// `cuboids.sensor_id` of `1`) are dropped.
+    fn cuboids(&self, path: &Path) -> Result<Vec<(String, Cuboid)>, Box<dyn Error>> {
+        let mut buf = Vec::new();
+        GzDecoder::new(File::open(path)?).read_to_end(&mut buf)?;
+
+        let df = DataFrame::from_pickle(&pickle::load(&buf)?)?;
+
+        let labels = df.string("label")?;
+        let yaws = df.f64("yaw")?;
+
+        let [x, y, z] = [
+            df.f64("position.x")?,
+            df.f64("position.y")?,
+            df.f64("position.z")?,
+        ];
+
+        let [dx, dy, dz] = [
+            df.f64("dimensions.x")?,
+            df.f64("dimensions.y")?,
+            df.f64("dimensions.z")?,
+        ];
+
+        let sensors = df
+            .i64("cuboids.sensor_id")
+            .unwrap_or_else(|_| vec![-1; df.len]);
+
+        let mut res = Vec::new();
+
+        for (i, label) in labels.into_iter().enumerate() {
+            if sensors[i] == 1 {
+                continue;
+            }
+
+            // The dimensions are along the x-axis (i.e., width) and y-axis
+            // (i.e., length) of the cuboid, which is rotated about the z-axis.
+            // As the length of a [`Cuboid`] is along its x-axis instead, the
+            // two are swapped such that the extent along each axis is kept.
+            let rotation = [(yaws[i] / 2.0).cos(), 0.0, 0.0, (yaws[i] / 2.0).sin()];
+            let cuboid = Cuboid::new([x[i], y[i], z[i]], [dy[i], dx[i], dz[i]], rotation);
+
+            res.push((label, cuboid));
+        }
+
+        Ok(res)
+    }
+
+    /// Project the set of cuboids onto a camera.
+    ///
+    /// The cuboids and the pose of the camera are both with respect to the
+    /// world coordinate system; therefore, the cuboids are moved into the
+    /// camera directly.
+    fn annotations(
+        &self,
+        cuboids: &[(String, Cuboid)],
+        pose: &PandaPose,
+        camera: &PandaCamera,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for (label, cuboid) in cuboids {
+            let a = cuboid
+                .clone()
+                .into_frame(pose.translation(), pose.rotation());
+
+            if a.inside(camera.view, WIDTH as f64, HEIGHT as f64) {
+                res.entry(label.clone()).or_default().push(Annotation::new(
+                    label.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(a.region(camera.view)),
+                ));
+            }
+        }
+
+        res
+    }
+}
+
+impl Schema for PandaSet<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let mut datastreams = Vec::new();
+
+        for sequence in self.sequences()? {
+            let name = sequence.file_name().unwrap().to_string_lossy().into_owned();
+
+            let error = |e: Box<dyn Error>| PandaSetError::from(format!("{}: {}", name, e));
+
+            let cameras = self.cameras(&sequence).map_err(error)?;
+            let length = cameras.iter().map(|x| x.poses.len()).max().unwrap_or(0);
+
+            // Construct the set of [`Frame`].
+            //
+            // This will loop through each frame and project its cuboids onto
+            // each camera with the pose of the camera at the frame. The test
+            // sequences do not provide cuboids and, as such, a missing file
+            // results in records without annotations.
+            let mut frames = Vec::new();
+
+            for index in 0..length {
+                let mut frame = Frame::new(index);
+
+                let path = sequence
+                    .join("annotations")
+                    .join("cuboids")
+                    .join(format!("{:02}.pkl.gz", index));
+
+                let cuboids = if path.is_file() {
+                    self.cuboids(&path)
+                        .map_err(|e| PandaSetError::from(format!("{}: {}", path.display(), e)))?
+                } else {
+                    Vec::new()
+                };
+
+                for camera in cameras.iter() {
+                    let path = sequence
+                        .join("camera")
+                        .join(&camera.name)
+                        .join(format!("{:02}.jpg", index));
+
+                    let image = if path.is_file() {
+                        Some(Image::new(ImageSource::File(path), WIDTH, HEIGHT))
+                    } else {
+                        None
+                    };
+
+                    let mut record = DetectionRecord::new(camera.channel.clone(), image);
+
+                    if let Some(pose) = camera.poses.get(index) {
+                        record.annotations = self.annotations(&cuboids, pose, camera);
+                    }
+
+                    // INSERT
+                    frame.samples.push(Sample::ObjectDetection(record));
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PandaSetDebug {
+    msg: String,
+}
+
+impl From<&str> for PandaSetDebug {
+    fn from(msg: &str) -> Self {
+        PandaSetDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for PandaSetDebug {
+    fn from(msg: String) -> Self {
+        PandaSetDebug { msg }
+    }
+}
+
+impl fmt::Display for PandaSetDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(
+            f,
+            "DEBUG({:020}s): stremf: pandaset: {}",
+            timestamp, self.msg
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PandaSetError {
+    msg: String,
+}
+
+impl From<&str> for PandaSetError {
+    fn from(msg: &str) -> Self {
+        PandaSetError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for PandaSetError {
+    fn from(msg: String) -> Self {
+        PandaSetError { msg }
+    }
+}
+
+impl fmt::Display for PandaSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pandaset: {}", self.msg)
+    }
+}
+
+impl Error for PandaSetError {}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+/// The intrinsics of a camera (i.e., `intrinsics.json`).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Intrinsics {
+    pub fx: f64,
+    pub fy: f64,
+    pub cx: f64,
+    pub cy: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Heading {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// The pose of a camera of a single frame (i.e., an entry of `poses.json`).
+///
+/// The pose is with respect to the world coordinate system.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Pose {
+    pub position: Position,
+    pub heading: Heading,
+}
+
+impl Pose {
+    /// x, y, z
+    pub fn translation(&self) -> [f64; 3] {
+        [self.position.x, self.position.y, self.position.z]
+    }
+
+    /// w, x, y, z
+    pub fn rotation(&self) -> [f64; 4] {
+        [
+            self.heading.w,
+            self.heading.x,
+            self.heading.y,
+            self.heading.z,
+        ]
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::pickle::{Ref, Value};
+
+/// A (one-dimensional) column of a [`DataFrame`].
+#[derive(Debug)]
+enum Column {
+    Float(Vec<f64>),
+    Int(Vec<i64>),
+    Bool(Vec<bool>),
+    Object(Vec<Ref>),
+}
+
+/// A NumPy array (i.e., `numpy.ndarray`).
+struct Array {
+    shape: Vec<usize>,
+    /// The data is stored in column-major (i.e., Fortran) order.
+    fortran: bool,
+    data: Column,
+}
+
+impl Array {
+    /// Decode a numeric buffer of a `dtype` (e.g., `<f8`).
+    fn decode(kind: &str, order: &str, buf: &[u8]) -> Result<Column, Box<dyn Error>> {
+        // The kind is a single character code followed by the size in bytes.
+        let (code, size) = match kind.get(..1) {
+            Some(code) => (code, kind[1..].parse::<usize>().unwrap_or(1)),
+            None => return Err(format!("unsupported dtype `{}`", kind).into()),
+        };
+
+        if size == 0 || !buf.chunks_exact(size).remainder().is_empty() {
+            return Err(format!("malformed buffer of dtype `{}`", kind).into());
+        }
+
+        let values = buf.chunks(size).map(|x| {
+            let mut bytes = x.to_vec();
+
+            if order == ">" {
+                bytes.reverse();
+            }
+
+            bytes
+        });
+
+        let column = match (code, size) {
+            ("f", 4) => Column::Float(
+                values
+                    .map(|x| f32::from_le_bytes(x[..].try_into().unwrap()) as f64)
+                    .collect(),
+            ),
+            ("f", 8) => Column::Float(
+                values
+                    .map(|x| f64::from_le_bytes(x[..].try_into().unwrap()))
+                    .collect(),
+            ),
+            ("i", 1..=8) => Column::Int(
+                values
+                    .map(|x| {
+                        let fill = if x[size - 1] & 0x80 != 0 { 0xff } else { 0x00 };
+                        let mut buf = [fill; 8];
+                        buf[..size].copy_from_slice(&x);
+
+                        i64::from_le_bytes(buf)
+                    })
+                    .collect(),
+            ),
+            ("u", 1..=8) => Column::Int(
+                values
+                    .map(|x| {
+                        let mut buf = [0; 8];
+                        buf[..size].copy_from_slice(&x);
+
+                        i64::from_le_bytes(buf)
+                    })
+                    .collect(),
+            ),
+            ("b", 1) => Column::Bool(values.map(|x| x[0] != 0).collect()),
+            _ => return Err(format!("unsupported dtype `{}`", kind).into()),
+        };
+
+        Ok(column)
+    }
+
+    /// The kind (e.g., `f8`) and byte order (e.g., `<`) of a `numpy.dtype`.
+    fn dtype(value: &Value) -> Result<(String, String), Box<dyn Error>> {
+        let (args, state) = match value {
+            Value::Object { args, state, .. } => (args, state),
+            _ => return Err("malformed dtype".into()),
+        };
+
+        let kind = args
+            .borrow()
+            .items()
+            .and_then(|x| {
+                x.first()
+                    .and_then(|x| x.borrow().as_str().map(String::from))
+            })
+            .ok_or("malformed dtype")?;
+
+        // The byte order is the second item of the state (e.g., `<` or `|`).
+        let order = state
+            .as_ref()
+            .and_then(|x| x.borrow().items())
+            .and_then(|x| x.get(1).and_then(|x| x.borrow().as_str().map(String::from)))
+            .unwrap_or(String::from("<"));
+
+        Ok((kind, order))
+    }
+
+    fn shape(value: &Value) -> Result<Vec<usize>, Box<dyn Error>> {
+        value
+            .items()
+            .ok_or("malformed shape")?
+            .iter()
+            .map(|x| {
+                x.borrow()
+                    .as_i64()
+                    .map(|x| x as usize)
+                    .ok_or_else(|| "malformed shape".into())
+            })
+            .collect()
+    }
+
+    /// Reconstruct an array from its pickled form.
+    ///
+    /// An array is pickled through either `_reconstruct` (i.e., the array is
+    /// built with its shape, dtype, and data as its state) or `_frombuffer`
+    /// (i.e., a numeric array of pickle protocol 5).
+    fn from_value(value: &Value) -> Result<Self, Box<dyn Error>> {
+        let (args, state) = match value {
+            Value::Object { args, state, .. } => (args, state),
+            _ => return Err("not an array".into()),
+        };
+
+        // The module of NumPy is either `numpy.core` or `numpy._core` (>= 2.0).
+        let name = match value.global() {
+            Some((module, name)) if module.starts_with("numpy") => name,
+            _ => return Err("not an array".into()),
+        };
+
+        match name.as_str() {
+            "_reconstruct" => {
+                let state = state
+                    .as_ref()
+                    .and_then(|x| x.borrow().items())
+                    .ok_or("malformed array")?;
+
+                if state.len() < 5 {
+                    return Err("malformed array".into());
+                }
+
+                let shape = Self::shape(&state[1].borrow())?;
+                let (kind, order) = Self::dtype(&state[2].borrow())?;
+                let fortran = matches!(*state[3].borrow(), Value::Bool(true));
+
+                let data = match &*state[4].borrow() {
+                    Value::List(x) => Column::Object(x.clone()),
+                    Value::Bytes(x) => Self::decode(&kind, &order, x)?,
+                    Value::String(x) => Self::decode(&kind, &order, x.as_bytes())?,
+                    _ => return Err("malformed array data".into()),
+                };
+
+                Ok(Array {
+                    shape,
+                    fortran,
+                    data,
+                })
+            }
+            "_frombuffer" => {
+                let args = args.borrow().items().ok_or("malformed array")?;
+
+                if args.len() < 4 {
+                    return Err("malformed array".into());
+                }
+
+                let (kind, order) = Self::dtype(&args[1].borrow())?;
+                let shape = Self::shape(&args[2].borrow())?;
+                let fortran = args[3].borrow().as_str() == Some("F");
+
+                let data = match &*args[0].borrow() {
+                    Value::Bytes(x) => Self::decode(&kind, &order, x)?,
+                    _ => return Err("malformed array data".into()),
+                };
+
+                Ok(Array {
+                    shape,
+                    fortran,
+                    data,
+                })
+            }
+            _ => Err("not an array".into()),
+        }
+    }
+
+    /// Split a two-dimensional array into its rows (i.e., the columns of a
+    /// block).
+    fn rows(self) -> Result<Vec<Column>, Box<dyn Error>> {
+        let (rows, cols) = match self.shape[..] {
+            [rows, cols] => (rows, cols),
+            [cols] => (1, cols),
+            _ => return Err(format!("unsupported array shape {:?}", self.shape).into()),
+        };
+
+        let len = match &self.data {
+            Column::Float(x) => x.len(),
+            Column::Int(x) => x.len(),
+            Column::Bool(x) => x.len(),
+            Column::Object(x) => x.len(),
+        };
+
+        if rows.checked_mul(cols) != Some(len) {
+            return Err(format!("array of shape {:?} with {} elements", self.shape, len).into());
+        }
+
+        let index = |i: usize, j: usize| {
+            if self.fortran {
+                j * rows + i
+            } else {
+                i * cols + j
+            }
+        };
+
+        Ok((0..rows)
+            .map(|i| match &self.data {
+                Column::Float(x) => Column::Float((0..cols).map(|j| x[index(i, j)]).collect()),
+                Column::Int(x) => Column::Int((0..cols).map(|j| x[index(i, j)]).collect()),
+                Column::Bool(x) => Column::Bool((0..cols).map(|j| x[index(i, j)]).collect()),
+                Column::Object(x) => {
+                    Column::Object((0..cols).map(|j| x[index(i, j)].clone()).collect())
+                }
+            })
+            .collect())
+    }
+}
+
+/// A pandas `DataFrame`, as read from a pickle.
+///
+/// The frame is reconstructed from the state of its `BlockManager` where each
+/// block is a two-dimensional array of a single dtype and its set of column
+/// names. The blocks that are not NumPy arrays (e.g., categoricals) are
+/// skipped.
+pub struct DataFrame {
+    columns: HashMap<String, Column>,
+    pub len: usize,
+}
+
+impl DataFrame {
+    /// The set of names of a pandas `Index`.
+    fn names(value: &Value) -> Result<Vec<String>, Box<dyn Error>> {
+        let args = match value {
+            Value::Object { args, .. } => args.borrow().items().ok_or("malformed index")?,
+            _ => return Err("malformed index".into()),
+        };
+
+        let data = args
+            .get(1)
+            .and_then(|x| x.borrow().get("data"))
+            .ok_or("malformed index")?;
+
+        let array = Array::from_value(&data.borrow())?;
+
+        match array.data {
+            Column::Object(x) => Ok(x
+                .iter()
+                .map(|x| match &*x.borrow() {
+                    Value::String(x) => x.clone(),
+                    x => format!("{:?}", x),
+                })
+                .collect()),
+            _ => Err("unsupported index".into()),
+        }
+    }
+
+    pub fn from_pickle(value: &Ref) -> Result<Self, Box<dyn Error>> {
+        let state = match &*value.borrow() {
+            Value::Object {
+                state: Some(state), ..
+            } => state.clone(),
+            _ => return Err("not a DataFrame".into()),
+        };
+
+        // The `BlockManager` is named `_mgr` (pandas >= 1.1) or `_data`.
+        let manager = state
+            .borrow()
+            .get("_mgr")
+            .or_else(|| state.borrow().get("_data"))
+            .ok_or("missing DataFrame `_mgr`")?;
+
+        let state = match &*manager.borrow() {
+            Value::Object {
+                state: Some(state), ..
+            } => state.borrow().items().ok_or("malformed BlockManager")?,
+            _ => return Err("malformed BlockManager".into()),
+        };
+
+        if state.len() < 3 {
+            return Err("malformed BlockManager".into());
+        }
+
+        let values = state[1].borrow().items().ok_or("malformed BlockManager")?;
+        let items = state[2].borrow().items().ok_or("malformed BlockManager")?;
+
+        let mut columns = HashMap::new();
+        let mut len = 0;
+
+        for (values, items) in values.iter().zip(items.iter()) {
+            let array = match Array::from_value(&values.borrow()) {
+                Ok(array) => array,
+                Err(_) => continue,
+            };
+
+            len = array.shape.last().copied().unwrap_or(0);
+
+            for (name, column) in Self::names(&items.borrow())?.into_iter().zip(array.rows()?) {
+                columns.insert(name, column);
+            }
+        }
+
+        Ok(DataFrame { columns, len })
+    }
+
+    fn column(&self, name: &str) -> Result<&Column, Box<dyn Error>> {
+        self.columns
+            .get(name)
+            .ok_or_else(|| format!("missing column `{}`", name).into())
+    }
+
+    pub fn f64(&self, name: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+        Ok(match self.column(name)? {
+            Column::Float(x) => x.clone(),
+            Column::Int(x) => x.iter().map(|x| *x as f64).collect(),
+            Column::Bool(x) => x.iter().map(|x| *x as i64 as f64).collect(),
+            Column::Object(x) => x
+                .iter()
+                .map(|x| x.borrow().as_f64().unwrap_or(f64::NAN))
+                .collect(),
+        })
+    }
+
+    pub fn i64(&self, name: &str) -> Result<Vec<i64>, Box<dyn Error>> {
+        Ok(match self.column(name)? {
+            Column::Float(x) => x.iter().map(|x| *x as i64).collect(),
+            Column::Int(x) => x.clone(),
+            Column::Bool(x) => x.iter().map(|x| *x as i64).collect(),
+            Column::Object(x) => x
+                .iter()
+                .map(|x| x.borrow().as_i64().unwrap_or_default())
+                .collect(),
+        })
+    }
+
+    pub fn string(&self, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        match self.column(name)? {
+            Column::Object(x) => Ok(x
+                .iter()
+                .map(|x| x.borrow().as_str().unwrap_or_default().to_string())
+                .collect()),
+            _ => Err(format!("column `{}` is not a string", name).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::pickle;
+    use super::*;
+
+    /// A pickled `DataFrame` (protocol 5) of a float, object, and int block.
+    const CUBOIDS: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/pandaset/cuboids.pkl"
+    ));
+
+    #[test]
+    fn from_pickle() {
+        let df = DataFrame::from_pickle(&pickle::load(CUBOIDS).unwrap()).unwrap();
+
+        assert_eq!(df.len, 3);
+        assert_eq!(df.f64("position.x").unwrap(), vec![1.5, 2.5, 3.5]);
+        assert_eq!(df.f64("position.y").unwrap(), vec![-2.0, -3.0, -4.0]);
+        assert_eq!(df.i64("cuboids.sensor_id").unwrap(), vec![0, -1, 1]);
+        assert_eq!(
+            df.string("label").unwrap(),
+            vec!["Car", "Pedestrian", "Car"]
+        );
+        assert!(df.f64("yaw").is_err());
+    }
+
+    #[test]
+    fn decode() {
+        let buf = [1i16.to_le_bytes(), (-2i16).to_le_bytes()].concat();
+
+        match Array::decode("i2", "<", &buf).unwrap() {
+            Column::Int(x) => assert_eq!(x, vec![1, -2]),
+            x => panic!("unexpected column {:?}", x),
+        }
+
+        match Array::decode("u2", ">", &buf).unwrap() {
+            Column::Int(x) => assert_eq!(x, vec![256, 0xfeff]),
+            x => panic!("unexpected column {:?}", x),
+        }
+    }
+
+    #[test]
+    fn malformed_dtype() {
+        assert!(Array::decode("", "<", &[0; 8]).is_err());
+        assert!(Array::decode("é8", "<", &[0; 8]).is_err());
+        assert!(Array::decode("i16", "<", &[0; 16]).is_err());
+        assert!(Array::decode("f8", "<", &[0; 6]).is_err());
+    }
+
+    #[test]
+    fn malformed_shape() {
+        let array = Array {
+            shape: vec![2, 3],
+            fortran: false,
+            data: Column::Float(vec![0.0; 5]),
+        };
+
+        assert!(array.rows().is_err());
+    }
+}
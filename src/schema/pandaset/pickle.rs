@@ -0,0 +1,559 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::rc::Rc;
+
+/// A reference to a (shared) value of a pickle.
+///
+/// The values are shared as the memo may reference the same value more than
+/// once and a value may be mutated (e.g., `APPENDS`) after it is memoized.
+pub type Ref = Rc<RefCell<Value>>;
+
+/// A value of a pickle.
+///
+/// Only the values required to reconstruct the structure of the objects are
+/// supported. A class is never instantiated; instead, an [`Value::Object`]
+/// records how it would be constructed.
+#[derive(Debug)]
+pub enum Value {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    List(Vec<Ref>),
+    Tuple(Vec<Ref>),
+    Dict(Vec<(Ref, Ref)>),
+    Set(Vec<Ref>),
+    /// A class or function (i.e., its module and name).
+    Global(String, String),
+    /// A call of a class or function (i.e., `REDUCE` or `NEWOBJ`) along with
+    /// its state, if any (i.e., `BUILD`).
+    Object {
+        callable: Ref,
+        args: Ref,
+        state: Option<Ref>,
+    },
+}
+
+impl Value {
+    fn share(self) -> Ref {
+        Rc::new(RefCell::new(self))
+    }
+
+    /// The module and name of a [`Value::Global`] (or the callable of a
+    /// [`Value::Object`]).
+    pub fn global(&self) -> Option<(String, String)> {
+        match self {
+            Value::Global(module, name) => Some((module.clone(), name.clone())),
+            Value::Object { callable, .. } => callable.borrow().global(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(x) => Some(*x),
+            Value::Bool(x) => Some(*x as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(x) => Some(*x),
+            Value::Int(x) => Some(*x as f64),
+            Value::None => Some(f64::NAN),
+            _ => None,
+        }
+    }
+
+    /// The items of a [`Value::Tuple`] or [`Value::List`].
+    pub fn items(&self) -> Option<Vec<Ref>> {
+        match self {
+            Value::Tuple(x) | Value::List(x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+
+    /// Look up an item of a [`Value::Dict`] by its (string) key.
+    pub fn get(&self, key: &str) -> Option<Ref> {
+        match self {
+            Value::Dict(x) => x
+                .iter()
+                .find(|(k, _)| k.borrow().as_str() == Some(key))
+                .map(|(_, v)| v.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The opcodes of the pickle protocol (up to version 5).
+mod op {
+    pub const MARK: u8 = b'(';
+    pub const STOP: u8 = b'.';
+    pub const POP: u8 = b'0';
+    pub const POP_MARK: u8 = b'1';
+    pub const DUP: u8 = b'2';
+    pub const FLOAT: u8 = b'F';
+    pub const INT: u8 = b'I';
+    pub const BININT: u8 = b'J';
+    pub const BININT1: u8 = b'K';
+    pub const LONG: u8 = b'L';
+    pub const BININT2: u8 = b'M';
+    pub const NONE: u8 = b'N';
+    pub const BINSTRING: u8 = b'T';
+    pub const SHORT_BINSTRING: u8 = b'U';
+    pub const UNICODE: u8 = b'V';
+    pub const BINUNICODE: u8 = b'X';
+    pub const APPEND: u8 = b'a';
+    pub const BUILD: u8 = b'b';
+    pub const GLOBAL: u8 = b'c';
+    pub const DICT: u8 = b'd';
+    pub const EMPTY_DICT: u8 = b'}';
+    pub const APPENDS: u8 = b'e';
+    pub const GET: u8 = b'g';
+    pub const BINGET: u8 = b'h';
+    pub const LONG_BINGET: u8 = b'j';
+    pub const LIST: u8 = b'l';
+    pub const EMPTY_LIST: u8 = b']';
+    pub const PUT: u8 = b'p';
+    pub const BINPUT: u8 = b'q';
+    pub const LONG_BINPUT: u8 = b'r';
+    pub const REDUCE: u8 = b'R';
+    pub const SETITEM: u8 = b's';
+    pub const TUPLE: u8 = b't';
+    pub const EMPTY_TUPLE: u8 = b')';
+    pub const SETITEMS: u8 = b'u';
+    pub const BINFLOAT: u8 = b'G';
+    pub const BINBYTES: u8 = b'B';
+    pub const SHORT_BINBYTES: u8 = b'C';
+    pub const PROTO: u8 = 0x80;
+    pub const NEWOBJ: u8 = 0x81;
+    pub const TUPLE1: u8 = 0x85;
+    pub const TUPLE2: u8 = 0x86;
+    pub const TUPLE3: u8 = 0x87;
+    pub const NEWTRUE: u8 = 0x88;
+    pub const NEWFALSE: u8 = 0x89;
+    pub const LONG1: u8 = 0x8a;
+    pub const LONG4: u8 = 0x8b;
+    pub const SHORT_BINUNICODE: u8 = 0x8c;
+    pub const BINUNICODE8: u8 = 0x8d;
+    pub const BINBYTES8: u8 = 0x8e;
+    pub const EMPTY_SET: u8 = 0x8f;
+    pub const ADDITEMS: u8 = 0x90;
+    pub const FROZENSET: u8 = 0x91;
+    pub const NEWOBJ_EX: u8 = 0x92;
+    pub const STACK_GLOBAL: u8 = 0x93;
+    pub const MEMOIZE: u8 = 0x94;
+    pub const FRAME: u8 = 0x95;
+    pub const BYTEARRAY8: u8 = 0x96;
+}
+
+/// The pickle virtual machine.
+///
+/// A mark on the stack is represented by `None`.
+struct Machine<'a> {
+    buf: &'a [u8],
+    stack: Vec<Option<Ref>>,
+    memo: HashMap<usize, Ref>,
+}
+
+impl<'a> Machine<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        if n > self.buf.len() {
+            return Err("truncated pickle".into());
+        }
+
+        let (bytes, rest) = self.buf.split_at(n);
+        self.buf = rest;
+
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Box<dyn Error>> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    /// Read a (newline-terminated) line of text.
+    fn line(&mut self) -> Result<String, Box<dyn Error>> {
+        let n = self
+            .buf
+            .iter()
+            .position(|x| *x == b'\n')
+            .ok_or("truncated pickle")?;
+
+        let line = String::from_utf8_lossy(self.take(n)?).into_owned();
+        self.take(1)?;
+
+        Ok(line)
+    }
+
+    fn string(&mut self, n: usize) -> Result<String, Box<dyn Error>> {
+        Ok(String::from_utf8(self.take(n)?.to_vec())?)
+    }
+
+    /// Decode a little-endian two's complement integer.
+    fn long(bytes: &[u8]) -> Result<i64, Box<dyn Error>> {
+        if bytes.len() > 8 {
+            return Err("integer out of range".into());
+        }
+
+        let fill = match bytes.last() {
+            Some(x) if x & 0x80 != 0 => 0xff,
+            _ => 0x00,
+        };
+
+        let mut buf = [fill; 8];
+        buf[..bytes.len()].copy_from_slice(bytes);
+
+        Ok(i64::from_le_bytes(buf))
+    }
+
+    fn push(&mut self, value: Value) {
+        self.stack.push(Some(value.share()));
+    }
+
+    fn pop(&mut self) -> Result<Ref, Box<dyn Error>> {
+        match self.stack.pop() {
+            Some(Some(x)) => Ok(x),
+            Some(None) => Err("unexpected mark".into()),
+            None => Err("stack underflow".into()),
+        }
+    }
+
+    fn top(&self) -> Result<Ref, Box<dyn Error>> {
+        match self.stack.last() {
+            Some(Some(x)) => Ok(x.clone()),
+            _ => Err("stack underflow".into()),
+        }
+    }
+
+    /// Pop the set of values up to (and including) the last mark.
+    fn mark(&mut self) -> Result<Vec<Ref>, Box<dyn Error>> {
+        let mut res = Vec::new();
+
+        loop {
+            match self.stack.pop() {
+                Some(Some(x)) => res.push(x),
+                Some(None) => break,
+                None => return Err("missing mark".into()),
+            }
+        }
+
+        res.reverse();
+
+        Ok(res)
+    }
+
+    fn memoize(&mut self, index: usize) -> Result<(), Box<dyn Error>> {
+        let top = self.top()?;
+        self.memo.insert(index, top);
+
+        Ok(())
+    }
+
+    fn get(&mut self, index: usize) -> Result<(), Box<dyn Error>> {
+        let value = self
+            .memo
+            .get(&index)
+            .ok_or_else(|| format!("missing memo `{}`", index))?
+            .clone();
+
+        self.stack.push(Some(value));
+
+        Ok(())
+    }
+
+    /// Extend the list (or set) on top of the stack.
+    fn extend(&mut self, items: Vec<Ref>) -> Result<(), Box<dyn Error>> {
+        match &mut *self.top()?.borrow_mut() {
+            Value::List(x) | Value::Set(x) => x.extend(items),
+            _ => return Err("unsupported append".into()),
+        }
+
+        Ok(())
+    }
+
+    /// Insert a set of (key, value) pairs into the dict on top of the stack.
+    fn insert(&mut self, items: Vec<Ref>) -> Result<(), Box<dyn Error>> {
+        let mut pairs = Vec::new();
+
+        for pair in items.chunks(2) {
+            match pair {
+                [k, v] => pairs.push((k.clone(), v.clone())),
+                _ => return Err("odd number of dict items".into()),
+            }
+        }
+
+        match &mut *self.top()?.borrow_mut() {
+            Value::Dict(x) => x.extend(pairs),
+            _ => return Err("unsupported setitem".into()),
+        }
+
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<Ref, Box<dyn Error>> {
+        loop {
+            let op = self.u8()?;
+
+            match op {
+                op::PROTO => {
+                    self.u8()?;
+                }
+                op::FRAME => {
+                    self.u64()?;
+                }
+                op::STOP => return self.pop(),
+                op::MARK => self.stack.push(None),
+                op::POP => {
+                    self.stack.pop();
+                }
+                op::POP_MARK => {
+                    self.mark()?;
+                }
+                op::DUP => {
+                    let top = self.top()?;
+                    self.stack.push(Some(top));
+                }
+                op::NONE => self.push(Value::None),
+                op::NEWTRUE => self.push(Value::Bool(true)),
+                op::NEWFALSE => self.push(Value::Bool(false)),
+                op::INT => {
+                    let line = self.line()?;
+
+                    self.push(match line.as_str() {
+                        "00" => Value::Bool(false),
+                        "01" => Value::Bool(true),
+                        x => Value::Int(x.parse()?),
+                    });
+                }
+                op::LONG => {
+                    let line = self.line()?;
+                    self.push(Value::Int(line.trim_end_matches('L').parse()?));
+                }
+                op::BININT => {
+                    let x = self.u32()? as i32;
+                    self.push(Value::Int(x as i64));
+                }
+                op::BININT1 => {
+                    let x = self.u8()?;
+                    self.push(Value::Int(x as i64));
+                }
+                op::BININT2 => {
+                    let x = self.u16()?;
+                    self.push(Value::Int(x as i64));
+                }
+                op::LONG1 => {
+                    let n = self.u8()? as usize;
+                    let x = Self::long(self.take(n)?)?;
+                    self.push(Value::Int(x));
+                }
+                op::LONG4 => {
+                    let n = self.u32()? as usize;
+                    let x = Self::long(self.take(n)?)?;
+                    self.push(Value::Int(x));
+                }
+                op::FLOAT => {
+                    let line = self.line()?;
+                    self.push(Value::Float(line.parse()?));
+                }
+                op::BINFLOAT => {
+                    let x = f64::from_be_bytes(self.take(8)?.try_into()?);
+                    self.push(Value::Float(x));
+                }
+                op::SHORT_BINUNICODE => {
+                    let n = self.u8()? as usize;
+                    let x = self.string(n)?;
+                    self.push(Value::String(x));
+                }
+                op::BINUNICODE => {
+                    let n = self.u32()? as usize;
+                    let x = self.string(n)?;
+                    self.push(Value::String(x));
+                }
+                op::BINUNICODE8 => {
+                    let n = self.u64()? as usize;
+                    let x = self.string(n)?;
+                    self.push(Value::String(x));
+                }
+                op::UNICODE => {
+                    let line = self.line()?;
+                    self.push(Value::String(line));
+                }
+                op::SHORT_BINBYTES | op::SHORT_BINSTRING => {
+                    let n = self.u8()? as usize;
+                    let x = self.take(n)?.to_vec();
+                    self.push(Value::Bytes(x));
+                }
+                op::BINBYTES | op::BINSTRING => {
+                    let n = self.u32()? as usize;
+                    let x = self.take(n)?.to_vec();
+                    self.push(Value::Bytes(x));
+                }
+                op::BINBYTES8 | op::BYTEARRAY8 => {
+                    let n = self.u64()? as usize;
+                    let x = self.take(n)?.to_vec();
+                    self.push(Value::Bytes(x));
+                }
+                op::EMPTY_TUPLE => self.push(Value::Tuple(Vec::new())),
+                op::TUPLE => {
+                    let items = self.mark()?;
+                    self.push(Value::Tuple(items));
+                }
+                op::TUPLE1 | op::TUPLE2 | op::TUPLE3 => {
+                    let n = (op - op::TUPLE1 + 1) as usize;
+                    let mut items = Vec::new();
+
+                    for _ in 0..n {
+                        items.push(self.pop()?);
+                    }
+
+                    items.reverse();
+                    self.push(Value::Tuple(items));
+                }
+                op::EMPTY_LIST => self.push(Value::List(Vec::new())),
+                op::LIST => {
+                    let items = self.mark()?;
+                    self.push(Value::List(items));
+                }
+                op::APPEND => {
+                    let item = self.pop()?;
+                    self.extend(vec![item])?;
+                }
+                op::APPENDS | op::ADDITEMS => {
+                    let items = self.mark()?;
+                    self.extend(items)?;
+                }
+                op::EMPTY_DICT => self.push(Value::Dict(Vec::new())),
+                op::DICT => {
+                    let items = self.mark()?;
+                    self.push(Value::Dict(Vec::new()));
+                    self.insert(items)?;
+                }
+                op::SETITEM => {
+                    let v = self.pop()?;
+                    let k = self.pop()?;
+                    self.insert(vec![k, v])?;
+                }
+                op::SETITEMS => {
+                    let items = self.mark()?;
+                    self.insert(items)?;
+                }
+                op::EMPTY_SET => self.push(Value::Set(Vec::new())),
+                op::FROZENSET => {
+                    let items = self.mark()?;
+                    self.push(Value::Set(items));
+                }
+                op::GLOBAL => {
+                    let module = self.line()?;
+                    let name = self.line()?;
+                    self.push(Value::Global(module, name));
+                }
+                op::STACK_GLOBAL => {
+                    let name = self.pop()?;
+                    let module = self.pop()?;
+
+                    let global = match (&*module.borrow(), &*name.borrow()) {
+                        (Value::String(module), Value::String(name)) => {
+                            Value::Global(module.clone(), name.clone())
+                        }
+                        _ => return Err("malformed global".into()),
+                    };
+
+                    self.push(global);
+                }
+                op::REDUCE | op::NEWOBJ => {
+                    let args = self.pop()?;
+                    let callable = self.pop()?;
+
+                    self.push(Value::Object {
+                        callable,
+                        args,
+                        state: None,
+                    });
+                }
+                op::NEWOBJ_EX => {
+                    self.pop()?;
+                    let args = self.pop()?;
+                    let callable = self.pop()?;
+
+                    self.push(Value::Object {
+                        callable,
+                        args,
+                        state: None,
+                    });
+                }
+                op::BUILD => {
+                    let value = self.pop()?;
+
+                    match &mut *self.top()?.borrow_mut() {
+                        Value::Object { state, .. } => *state = Some(value),
+                        _ => return Err("unsupported build".into()),
+                    }
+                }
+                op::PUT => {
+                    let index = self.line()?.parse()?;
+                    self.memoize(index)?;
+                }
+                op::BINPUT => {
+                    let index = self.u8()? as usize;
+                    self.memoize(index)?;
+                }
+                op::LONG_BINPUT => {
+                    let index = self.u32()? as usize;
+                    self.memoize(index)?;
+                }
+                op::MEMOIZE => {
+                    let index = self.memo.len();
+                    self.memoize(index)?;
+                }
+                op::GET => {
+                    let index = self.line()?.parse()?;
+                    self.get(index)?;
+                }
+                op::BINGET => {
+                    let index = self.u8()? as usize;
+                    self.get(index)?;
+                }
+                op::LONG_BINGET => {
+                    let index = self.u32()? as usize;
+                    self.get(index)?;
+                }
+                x => return Err(format!("unsupported opcode `0x{:02x}`", x).into()),
+            }
+        }
+    }
+}
+
+/// Load a pickle into its [`Value`].
+pub fn load(buf: &[u8]) -> Result<Ref, Box<dyn Error>> {
+    let mut machine = Machine {
+        buf,
+        stack: Vec::new(),
+        memo: HashMap::new(),
+    };
+
+    machine.run()
+}
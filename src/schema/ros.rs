@@ -0,0 +1,6 @@
+pub mod decode;
+pub mod definition;
+pub mod detection;
+pub mod frame;
+pub mod options;
+pub mod value;
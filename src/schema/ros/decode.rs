@@ -0,0 +1,327 @@
+use super::definition::{Field, Kind, Length, Primitive, Registry};
+use super::value::Value;
+
+/// A decoder of serialized messages.
+///
+/// The ROS1 serialization format stores each value in little-endian without
+/// any padding. The CDR serialization format (i.e., ROS2) is prefixed by a
+/// four-byte encapsulation header, which provides the byte order, and each
+/// primitive is aligned to its size (relative to the end of the header). In
+/// both formats, the strings and dynamic arrays are prefixed by their length
+/// as a `u32`.
+pub struct Decoder<'a> {
+    registry: &'a Registry,
+    buf: &'a [u8],
+    pos: usize,
+    /// The start of the aligned data, if aligned (i.e., CDR).
+    origin: Option<usize>,
+    big: bool,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(registry: &'a Registry, buf: &'a [u8]) -> Self {
+        Self {
+            registry,
+            buf,
+            pos: 0,
+            origin: None,
+            big: false,
+        }
+    }
+
+    /// Create a decoder of a CDR-encoded message.
+    ///
+    /// Only the plain CDR representations (i.e., `CDR_BE` and `CDR_LE`) are
+    /// supported.
+    pub fn cdr(registry: &'a Registry, buf: &'a [u8]) -> Result<Self, String> {
+        let big = match buf.get(..2) {
+            Some([0x00, 0x00]) => true,
+            Some([0x00, 0x01]) => false,
+            Some([a, b]) => {
+                return Err(format!(
+                    "unsupported CDR representation `0x{:02x}{:02x}`",
+                    a, b
+                ))
+            }
+            _ => return Err(String::from("missing CDR encapsulation header")),
+        };
+
+        Ok(Self {
+            registry,
+            buf,
+            pos: 4,
+            origin: Some(4),
+            big,
+        })
+    }
+
+    /// Decode the root message of the registry.
+    pub fn decode(mut self) -> Result<Value, String> {
+        let root = self.registry.root.clone();
+        self.message(&root)
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+
+        if end > self.buf.len() {
+            return Err(format!(
+                "unexpected end of message (at byte {} of {})",
+                self.pos,
+                self.buf.len()
+            ));
+        }
+
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+
+        Ok(bytes)
+    }
+
+    /// Read the (little-endian) bytes of a primitive of size `N`.
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        if let Some(origin) = self.origin {
+            let offset = (self.pos - origin) % N;
+
+            if offset != 0 {
+                self.pos += N - offset;
+            }
+        }
+
+        let mut bytes: [u8; N] = self.take(N)?.try_into().unwrap();
+
+        if self.big {
+            bytes.reverse();
+        }
+
+        Ok(bytes)
+    }
+
+    fn length(&mut self) -> Result<usize, String> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+
+    fn primitive(&mut self, primitive: Primitive) -> Result<Value, String> {
+        Ok(match primitive {
+            Primitive::Bool => Value::Bool(self.array::<1>()?[0] != 0),
+            Primitive::Int8 => Value::Int(i8::from_le_bytes(self.array()?) as i64),
+            Primitive::UInt8 => Value::UInt(u8::from_le_bytes(self.array()?) as u64),
+            Primitive::Int16 => Value::Int(i16::from_le_bytes(self.array()?) as i64),
+            Primitive::UInt16 => Value::UInt(u16::from_le_bytes(self.array()?) as u64),
+            Primitive::Int32 => Value::Int(i32::from_le_bytes(self.array()?) as i64),
+            Primitive::UInt32 => Value::UInt(u32::from_le_bytes(self.array()?) as u64),
+            Primitive::Int64 => Value::Int(i64::from_le_bytes(self.array()?)),
+            Primitive::UInt64 => Value::UInt(u64::from_le_bytes(self.array()?)),
+            Primitive::Float32 => Value::Float(f32::from_le_bytes(self.array()?) as f64),
+            Primitive::Float64 => Value::Float(f64::from_le_bytes(self.array()?)),
+            Primitive::String => {
+                let n = self.length()?;
+                let bytes = self.take(n)?;
+
+                // The CDR strings include the null terminator.
+                Value::String(
+                    String::from_utf8_lossy(bytes)
+                        .trim_end_matches('\0')
+                        .to_string(),
+                )
+            }
+            Primitive::Time => {
+                let sec = u32::from_le_bytes(self.array()?) as i64;
+                let nsec = u32::from_le_bytes(self.array()?) as i64;
+
+                Value::Time(sec * 1_000_000_000 + nsec)
+            }
+            Primitive::Duration => {
+                let sec = i32::from_le_bytes(self.array()?) as i64;
+                let nsec = i32::from_le_bytes(self.array()?) as i64;
+
+                Value::Time(sec * 1_000_000_000 + nsec)
+            }
+        })
+    }
+
+    fn scalar(&mut self, kind: &Kind) -> Result<Value, String> {
+        match kind {
+            Kind::Primitive(primitive) => self.primitive(*primitive),
+            Kind::Message(name) => self.message(name),
+        }
+    }
+
+    fn field(&mut self, field: &Field) -> Result<Value, String> {
+        let n = match field.length {
+            Length::Scalar => return self.scalar(&field.kind),
+            Length::Fixed(n) => n,
+            Length::Dynamic => self.length()?,
+        };
+
+        if field.kind == Kind::Primitive(Primitive::UInt8) {
+            return Ok(Value::Bytes(self.take(n)?.to_vec()));
+        }
+
+        let mut values = Vec::with_capacity(n.min(self.buf.len()));
+
+        for _ in 0..n {
+            values.push(self.scalar(&field.kind)?);
+        }
+
+        Ok(Value::Array(values))
+    }
+
+    fn message(&mut self, name: &str) -> Result<Value, String> {
+        let registry = self.registry;
+        let mut values = Vec::new();
+
+        for field in registry.fields(name)? {
+            let value = self
+                .field(field)
+                .map_err(|e| format!("{}.{}: {}", name, field.name, e))?;
+
+            values.push((field.name.clone(), value));
+        }
+
+        Ok(Value::Message(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROS1: &str = "\
+Header header
+uint8[] data
+float32[2] point
+================================================================================
+MSG: std_msgs/Header
+uint32 seq
+time stamp
+string frame_id
+";
+
+    const ROS2: &str = "\
+uint8 kind
+float64 score
+string label
+int16[2] offset
+";
+
+    fn field<'a>(value: &'a Value, path: &[&str]) -> &'a Value {
+        path.iter()
+            .fold(value, |value, name| value.get(name).unwrap())
+    }
+
+    #[test]
+    fn ros1() {
+        let registry = Registry::parse("pkg/Message", ROS1).unwrap();
+
+        let buf = [
+            &7u32.to_le_bytes()[..],
+            &1u32.to_le_bytes(),
+            &2u32.to_le_bytes(),
+            &3u32.to_le_bytes(),
+            b"cam",
+            &2u32.to_le_bytes(),
+            &[0xab, 0xcd],
+            &1.5f32.to_le_bytes(),
+            &(-2.0f32).to_le_bytes(),
+        ]
+        .concat();
+
+        let value = Decoder::new(&registry, &buf).decode().unwrap();
+
+        assert_eq!(field(&value, &["header", "seq"]), &Value::UInt(7));
+        assert_eq!(
+            field(&value, &["header", "stamp"]),
+            &Value::Time(1_000_000_002)
+        );
+        assert_eq!(
+            field(&value, &["header", "frame_id"]),
+            &Value::String(String::from("cam"))
+        );
+        assert_eq!(field(&value, &["data"]), &Value::Bytes(vec![0xab, 0xcd]));
+        assert_eq!(
+            field(&value, &["point"]),
+            &Value::Array(vec![Value::Float(1.5), Value::Float(-2.0)])
+        );
+    }
+
+    #[test]
+    fn cdr_le() {
+        let registry = Registry::parse("pkg/msg/Message", ROS2).unwrap();
+
+        // Each primitive is aligned to its size relative to the end of the
+        // encapsulation header (e.g., the `float64` follows seven bytes of
+        // padding).
+        let buf = [
+            &[0x00, 0x01, 0x00, 0x00][..],
+            &[3, 0, 0, 0, 0, 0, 0, 0],
+            &0.5f64.to_le_bytes(),
+            &4u32.to_le_bytes(),
+            b"car\0",
+            &(-1i16).to_le_bytes(),
+            &2i16.to_le_bytes(),
+        ]
+        .concat();
+
+        let value = Decoder::cdr(&registry, &buf).unwrap().decode().unwrap();
+
+        assert_eq!(
+            value,
+            Value::Message(vec![
+                (String::from("kind"), Value::UInt(3)),
+                (String::from("score"), Value::Float(0.5)),
+                (String::from("label"), Value::String(String::from("car"))),
+                (
+                    String::from("offset"),
+                    Value::Array(vec![Value::Int(-1), Value::Int(2)])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn cdr_be() {
+        let registry = Registry::parse("pkg/msg/Message", ROS2).unwrap();
+
+        let buf = [
+            &[0x00, 0x00, 0x00, 0x00][..],
+            &[3, 0, 0, 0, 0, 0, 0, 0],
+            &0.5f64.to_be_bytes(),
+            &4u32.to_be_bytes(),
+            b"car\0",
+            &(-1i16).to_be_bytes(),
+            &2i16.to_be_bytes(),
+        ]
+        .concat();
+
+        let value = Decoder::cdr(&registry, &buf).unwrap().decode().unwrap();
+
+        assert_eq!(field(&value, &["score"]), &Value::Float(0.5));
+        assert_eq!(
+            field(&value, &["label"]),
+            &Value::String(String::from("car"))
+        );
+        assert_eq!(
+            field(&value, &["offset"]),
+            &Value::Array(vec![Value::Int(-1), Value::Int(2)])
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let registry = Registry::parse("pkg/msg/Message", ROS2).unwrap();
+        let buf = [0x00, 0x01, 0x00, 0x00, 3, 0, 0, 0];
+
+        let err = Decoder::cdr(&registry, &buf).unwrap().decode().unwrap_err();
+
+        assert!(err.starts_with("pkg/Message.score: unexpected end of message"));
+    }
+
+    #[test]
+    fn representation() {
+        let registry = Registry::parse("pkg/msg/Message", ROS2).unwrap();
+
+        assert!(Decoder::cdr(&registry, &[0x00, 0x03, 0x00, 0x00]).is_err());
+        assert!(Decoder::cdr(&registry, &[0x00]).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+/// A primitive type of the ROS message definition language.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Primitive {
+    Bool,
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Int64,
+    UInt64,
+    Float32,
+    Float64,
+    String,
+    /// Only present in ROS1 (i.e., `time` and `duration`).
+    Time,
+    Duration,
+}
+
+impl Primitive {
+    fn parse(name: &str) -> Option<Self> {
+        // The bounded strings of ROS2 (e.g., `string<=10`) are treated as
+        // regular strings.
+        let name = name.split("<=").next().unwrap_or(name);
+
+        Some(match name {
+            "bool" => Primitive::Bool,
+            "int8" => Primitive::Int8,
+            "uint8" | "byte" | "char" | "octet" => Primitive::UInt8,
+            "int16" => Primitive::Int16,
+            "uint16" => Primitive::UInt16,
+            "int32" => Primitive::Int32,
+            "uint32" => Primitive::UInt32,
+            "int64" => Primitive::Int64,
+            "uint64" => Primitive::UInt64,
+            "float32" => Primitive::Float32,
+            "float64" => Primitive::Float64,
+            "string" | "wstring" => Primitive::String,
+            "time" => Primitive::Time,
+            "duration" => Primitive::Duration,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    Primitive(Primitive),
+    /// The fully-qualified name of the message (e.g., `std_msgs/Header`).
+    Message(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    Scalar,
+    Fixed(usize),
+    /// The unbounded (and bounded) sequences.
+    Dynamic,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub kind: Kind,
+    pub length: Length,
+}
+
+/// The set of message definitions of a topic.
+///
+/// The definition of a topic (e.g., the `message_definition` of a ROS1
+/// connection or a `ros2msg` schema) consists of the root message followed by
+/// each of its dependencies, separated by a line of `=` and introduced by a
+/// `MSG: <package>/<name>` line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Registry {
+    pub root: String,
+    messages: HashMap<String, Vec<Field>>,
+}
+
+/// Normalize the name of a message.
+///
+/// The ROS2 names include the `msg` namespace (e.g., `std_msgs/msg/Header`)
+/// whereas the ROS1 names do not; therefore, the namespace is removed.
+pub fn normalize(name: &str) -> String {
+    name.trim().replace("/msg/", "/")
+}
+
+/// Resolve the fully-qualified name of a (complex) type.
+///
+/// An unqualified type refers to the package of the message it is used in,
+/// except for `Header`, which always refers to `std_msgs/Header`.
+fn resolve(name: &str, package: &str) -> String {
+    if name.contains('/') {
+        normalize(name)
+    } else if name == "Header" {
+        String::from("std_msgs/Header")
+    } else {
+        format!("{}/{}", package, name)
+    }
+}
+
+/// Parse a single field of a message.
+///
+/// Any constant (e.g., `uint8 CAR=1`) or empty line results in [`None`].
+fn field(line: &str, package: &str) -> Result<Option<Field>, String> {
+    let line = line.split('#').next().unwrap_or("").trim();
+
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let (kind, rest) = line
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("invalid field `{}`", line))?;
+
+    if rest.contains('=') {
+        return Ok(None);
+    }
+
+    // The default value (ROS2) may follow the name and is ignored.
+    let name = rest.split_whitespace().next().unwrap().to_string();
+
+    let (kind, length) = match kind.split_once('[') {
+        Some((kind, size)) => {
+            let size = size.trim_end_matches(']');
+
+            let length = if size.is_empty() || size.starts_with("<=") {
+                Length::Dynamic
+            } else {
+                Length::Fixed(
+                    size.parse()
+                        .map_err(|_| format!("invalid array size `{}`", size))?,
+                )
+            };
+
+            (kind, length)
+        }
+        None => (kind, Length::Scalar),
+    };
+
+    let kind = match Primitive::parse(kind) {
+        Some(primitive) => Kind::Primitive(primitive),
+        None => Kind::Message(resolve(kind, package)),
+    };
+
+    Ok(Some(Field { name, kind, length }))
+}
+
+impl Registry {
+    /// Parse the set of message definitions of a root message.
+    pub fn parse(root: &str, text: &str) -> Result<Self, String> {
+        let root = normalize(root);
+
+        let mut messages = HashMap::new();
+        let mut name = root.clone();
+        let mut fields = Vec::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+
+            if trimmed.len() >= 3 && trimmed.chars().all(|x| x == '=') {
+                messages.insert(name.clone(), std::mem::take(&mut fields));
+                continue;
+            }
+
+            if let Some(dependency) = trimmed.strip_prefix("MSG:") {
+                name = normalize(dependency);
+                continue;
+            }
+
+            let package = name.split('/').next().unwrap_or("");
+
+            if let Some(field) = field(line, package)? {
+                fields.push(field);
+            }
+        }
+
+        messages.insert(name, fields);
+
+        Ok(Registry { root, messages })
+    }
+
+    /// The set of fields of a message.
+    pub fn fields(&self, name: &str) -> Result<&[Field], String> {
+        self.messages
+            .get(name)
+            .map(|x| x.as_slice())
+            .ok_or_else(|| format!("missing definition of `{}`", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let text = "\
+# A comment
+uint8 CAR=1
+Header header
+Detection2D[] detections
+string<=10 name \"default\"
+float64[<=4] values
+================================================================================
+MSG: std_msgs/msg/Header
+builtin_interfaces/Time stamp
+string frame_id
+================================================================================
+MSG: vision_msgs/Detection2D
+float64[4] bbox
+";
+
+        let registry = Registry::parse("vision_msgs/msg/Detection2DArray", text).unwrap();
+
+        assert_eq!(registry.root, "vision_msgs/Detection2DArray");
+        assert_eq!(
+            registry.fields("vision_msgs/Detection2DArray").unwrap(),
+            &[
+                Field {
+                    name: String::from("header"),
+                    kind: Kind::Message(String::from("std_msgs/Header")),
+                    length: Length::Scalar,
+                },
+                Field {
+                    name: String::from("detections"),
+                    kind: Kind::Message(String::from("vision_msgs/Detection2D")),
+                    length: Length::Dynamic,
+                },
+                Field {
+                    name: String::from("name"),
+                    kind: Kind::Primitive(Primitive::String),
+                    length: Length::Scalar,
+                },
+                Field {
+                    name: String::from("values"),
+                    kind: Kind::Primitive(Primitive::Float64),
+                    length: Length::Dynamic,
+                },
+            ]
+        );
+
+        assert_eq!(
+            registry.fields("std_msgs/Header").unwrap()[0].kind,
+            Kind::Message(String::from("builtin_interfaces/Time"))
+        );
+        assert_eq!(
+            registry.fields("vision_msgs/Detection2D").unwrap()[0].length,
+            Length::Fixed(4)
+        );
+        assert!(registry.fields("builtin_interfaces/Time").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Registry::parse("pkg/Message", "float64[x] values").is_err());
+        assert!(Registry::parse("pkg/Message", "float64").is_err());
+    }
+}
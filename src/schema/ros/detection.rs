@@ -0,0 +1,209 @@
+use super::value::Value;
+
+/// A single `vision_msgs/Detection2D`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Detection {
+    /// The class of the most likely hypothesis.
+    pub class: String,
+    pub score: f64,
+    /// x, y, width, height (from the center)
+    pub bbox: [f64; 4],
+}
+
+/// A single `vision_msgs/Detection2DArray`.
+///
+/// The layout of the message differs across the releases of `vision_msgs`;
+/// therefore, the fields of both the ROS1 (i.e., an integer `id` and a
+/// `Pose2D` center) and ROS2 (i.e., a `hypothesis.class_id` and a nested
+/// `position` center) layouts are accepted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Detections {
+    /// The timestamp of the header (in nanoseconds), if set.
+    pub stamp: Option<i64>,
+    pub detections: Vec<Detection>,
+}
+
+/// Read a `builtin_interfaces/Time` (or `foxglove.Time`) timestamp.
+fn time(value: &Value) -> Option<i64> {
+    let nsec = value.get("nanosec").or(value.get("nsec"))?.as_i64()?;
+    let ns = value.get("sec")?.as_i64()? * 1_000_000_000 + nsec;
+
+    if ns == 0 {
+        None
+    } else {
+        Some(ns)
+    }
+}
+
+/// Read the timestamp of a `std_msgs/Header`.
+///
+/// The ROS1 header stores a `time` whereas the ROS2 header stores a
+/// `builtin_interfaces/Time` message.
+fn stamp(header: &Value) -> Option<i64> {
+    match header.get("stamp")? {
+        Value::Time(0) => None,
+        Value::Time(ns) => Some(*ns),
+        stamp => time(stamp),
+    }
+}
+
+/// Read a `x` and `y` coordinate (e.g., a `foxglove.Point2`).
+fn point(value: &Value) -> Option<(f64, f64)> {
+    Some((value.get("x")?.as_f64()?, value.get("y")?.as_f64()?))
+}
+
+/// Check if a `foxglove.PointsAnnotation` is a closed polyline.
+///
+/// The type is either the name (e.g., JSON) or the value of the enumeration.
+fn closed(annotation: &Value) -> bool {
+    match annotation.get("type") {
+        Some(Value::String(x)) => x == "LINE_LOOP",
+        Some(x) => x.as_i64() == Some(2),
+        None => false,
+    }
+}
+
+/// Read the class and score of a `vision_msgs/ObjectHypothesisWithPose`.
+fn hypothesis(result: &Value) -> Option<(String, f64)> {
+    let hypothesis = result.get("hypothesis").unwrap_or(result);
+
+    let class = match hypothesis.get("class_id").or(hypothesis.get("id"))? {
+        Value::String(x) => x.clone(),
+        x => x.as_i64()?.to_string(),
+    };
+
+    Some((class, hypothesis.get("score")?.as_f64()?))
+}
+
+impl Detection {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let bbox = value.get("bbox").ok_or("missing `bbox`")?;
+        let center = bbox.get("center").ok_or("missing `bbox.center`")?;
+        let center = center.get("position").unwrap_or(center);
+
+        let coordinate = |value: &Value, name: &str| {
+            value
+                .get(name)
+                .and_then(|x| x.as_f64())
+                .ok_or_else(|| format!("missing `{}`", name))
+        };
+
+        // The most likely hypothesis is selected as the class.
+        //
+        // If no hypothesis is provided, then the class is unknown.
+        let (class, score) = value
+            .get("results")
+            .and_then(|x| x.as_array())
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(hypothesis)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or_else(|| (String::from("unknown"), 1.0));
+
+        Ok(Detection {
+            class,
+            score,
+            bbox: [
+                coordinate(center, "x")?,
+                coordinate(center, "y")?,
+                coordinate(bbox, "size_x")?,
+                coordinate(bbox, "size_y")?,
+            ],
+        })
+    }
+}
+
+impl Detections {
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        let detections = value
+            .get("detections")
+            .and_then(|x| x.as_array())
+            .ok_or("missing `detections`")?
+            .iter()
+            .map(Detection::from_value)
+            .collect::<Result<Vec<Detection>, String>>()?;
+
+        Ok(Detections {
+            stamp: value.get("header").and_then(stamp),
+            detections,
+        })
+    }
+
+    /// Read a `foxglove.ImageAnnotations` message.
+    ///
+    /// No boxes are provided by the message; therefore, each closed polyline
+    /// (i.e., a `LINE_LOOP` points annotation) is converted into the box about
+    /// its points. Each box is labeled by the text annotation nearest to its
+    /// top-left corner, if any.
+    pub fn from_annotations(value: &Value) -> Result<Self, String> {
+        let texts = value
+            .get("texts")
+            .and_then(|x| x.as_array())
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|x| {
+                let position = point(x.get("position")?)?;
+
+                match x.get("text")? {
+                    Value::String(text) => Some((position, text.clone())),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<((f64, f64), String)>>();
+
+        let mut res = Detections {
+            stamp: None,
+            detections: Vec::new(),
+        };
+
+        for annotation in value
+            .get("points")
+            .and_then(|x| x.as_array())
+            .unwrap_or(&[])
+            .iter()
+            .filter(|x| closed(x))
+        {
+            let points = annotation
+                .get("points")
+                .and_then(|x| x.as_array())
+                .ok_or("missing `points`")?
+                .iter()
+                .map(|x| point(x).ok_or("invalid `points`"))
+                .collect::<Result<Vec<(f64, f64)>, &str>>()?;
+
+            if points.len() < 2 {
+                continue;
+            }
+
+            let xmin = points.iter().map(|x| x.0).fold(f64::INFINITY, f64::min);
+            let ymin = points.iter().map(|x| x.1).fold(f64::INFINITY, f64::min);
+            let xmax = points.iter().map(|x| x.0).fold(f64::NEG_INFINITY, f64::max);
+            let ymax = points.iter().map(|x| x.1).fold(f64::NEG_INFINITY, f64::max);
+
+            let distance = |(x, y): (f64, f64)| (x - xmin).powi(2) + (y - ymin).powi(2);
+
+            let class = texts
+                .iter()
+                .min_by(|a, b| distance(a.0).total_cmp(&distance(b.0)))
+                .map(|(_, x)| x.clone())
+                .unwrap_or_else(|| String::from("unknown"));
+
+            if res.stamp.is_none() {
+                res.stamp = annotation.get("timestamp").and_then(time);
+            }
+
+            res.detections.push(Detection {
+                class,
+                score: 1.0,
+                bbox: [
+                    (xmin + xmax) / 2.0,
+                    (ymin + ymax) / 2.0,
+                    xmax - xmin,
+                    ymax - ymin,
+                ],
+            });
+        }
+
+        Ok(res)
+    }
+}
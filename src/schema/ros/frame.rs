@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{Annotation, DetectionRecord};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use super::detection::Detections;
+use super::options::Options;
+
+type Topic = String;
+type Timestamp = i64;
+
+/// Group a set of detections (ordered by timestamp) into frames.
+///
+/// A message joins the current frame if its timestamp is within the
+/// tolerance of the first message of the frame and no other message of its
+/// topic is part of the frame; otherwise, a new frame is started.
+fn group(
+    detections: Vec<(Timestamp, Topic, Detections)>,
+    options: &Options,
+) -> Vec<BTreeMap<Topic, Detections>> {
+    let tolerance = (options.tolerance * 1e9) as Timestamp;
+
+    let mut groups: Vec<(Timestamp, BTreeMap<Topic, Detections>)> = Vec::new();
+
+    for (stamp, topic, detections) in detections {
+        match groups.last_mut() {
+            Some((start, group)) if stamp - *start <= tolerance && !group.contains_key(&topic) => {
+                group.insert(topic, detections);
+            }
+            _ => groups.push((stamp, BTreeMap::from([(topic, detections)]))),
+        }
+    }
+
+    groups.into_iter().map(|(_, x)| x).collect()
+}
+
+/// Construct the set of [`Frame`] of a set of detections.
+///
+/// Each frame only contains a record for the topics that published a message
+/// within the frame.
+pub fn frames(
+    mut detections: Vec<(Timestamp, Topic, Detections)>,
+    options: &Options,
+) -> Vec<Frame> {
+    detections.sort_by_key(|(stamp, _, _)| *stamp);
+
+    let mut frames = Vec::new();
+
+    for (index, group) in group(detections, options).into_iter().enumerate() {
+        let mut frame = Frame::new(index);
+
+        for (topic, detections) in group {
+            let mut record = DetectionRecord::new(options.channel(&topic), None);
+
+            for detection in detections.detections {
+                let [x, y, width, height] = detection.bbox;
+                let label = options.label(&detection.class);
+
+                record
+                    .annotations
+                    .entry(label.clone())
+                    .or_default()
+                    .push(Annotation::new(
+                        label,
+                        detection.score,
+                        BoundingBox::AxisAligned(aa::Region::new(Point::new(x, y), width, height)),
+                    ));
+            }
+
+            // INSERT
+            frame.samples.push(Sample::ObjectDetection(record));
+        }
+
+        frames.push(frame);
+    }
+
+    frames
+}
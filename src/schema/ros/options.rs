@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The set of options applied to ROS bags and MCAP files.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// The set of topics to import.
+    ///
+    /// If not provided, every topic of a supported message type is used.
+    pub topics: Option<Vec<String>>,
+    /// The mapping of topics (e.g., `/camera/front/detections`) to channels.
+    pub channels: HashMap<String, String>,
+    /// The mapping of class ids (e.g., `1`) to labels.
+    pub labels: HashMap<String, String>,
+    /// The maximum difference (in seconds) between the timestamps of the
+    /// messages of a single frame.
+    pub tolerance: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            topics: None,
+            channels: HashMap::new(),
+            labels: HashMap::new(),
+            tolerance: 0.05,
+        }
+    }
+}
+
+impl Options {
+    /// Check if the topic passes the set of filters.
+    pub fn keep(&self, topic: &str) -> bool {
+        self.topics
+            .as_ref()
+            .map(|x| x.iter().any(|x| x == topic))
+            .unwrap_or(true)
+    }
+
+    /// The channel of a topic.
+    ///
+    /// By default, the leading `/` is removed and each remaining `/` is
+    /// replaced by `::` (e.g., `/cam/front` becomes `cam::front`).
+    pub fn channel(&self, topic: &str) -> String {
+        self.channels
+            .get(topic)
+            .cloned()
+            .unwrap_or_else(|| topic.trim_start_matches('/').replace('/', "::"))
+    }
+
+    /// The label of a class.
+    pub fn label(&self, class: &str) -> String {
+        self.labels
+            .get(class)
+            .cloned()
+            .unwrap_or_else(|| class.to_string())
+    }
+}
@@ -0,0 +1,74 @@
+/// A dynamically-typed (deserialized) ROS message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    /// The `time` and `duration` of ROS1 (in nanoseconds).
+    Time(i64),
+    /// The arrays of `uint8` (e.g., image data) are kept as raw bytes.
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Message(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Find a field of a message by name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Message(fields) => fields.iter().find(|(x, _)| x == name).map(|(_, x)| x),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(x) => Some(*x as f64),
+            Value::UInt(x) => Some(*x as f64),
+            Value::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(x) => Some(*x),
+            Value::UInt(x) => Some(*x as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl From<&serde_json::Value> for Value {
+    /// Convert a JSON-encoded message.
+    ///
+    /// Each object is converted into a message (with its fields in the order
+    /// of the object), accordingly.
+    fn from(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Message(Vec::new()),
+            serde_json::Value::Bool(x) => Value::Bool(*x),
+            serde_json::Value::Number(x) => match (x.as_i64(), x.as_u64()) {
+                (Some(x), _) => Value::Int(x),
+                (None, Some(x)) => Value::UInt(x),
+                _ => Value::Float(x.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(x) => Value::String(x.clone()),
+            serde_json::Value::Array(x) => Value::Array(x.iter().map(Value::from).collect()),
+            serde_json::Value::Object(x) => Value::Message(
+                x.iter()
+                    .map(|(name, value)| (name.clone(), Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
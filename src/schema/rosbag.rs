@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::ros::decode::Decoder as RosDecoder;
+use super::ros::definition::{normalize, Registry as RosRegistry};
+use super::ros::detection::Detections as RosDetections;
+use super::ros::frame;
+use super::ros::options::Options as BagOptions;
+use super::Schema;
+
+use self::bag::{BagReader, Connection as BagConnection};
+
+mod bag;
+
+type Topic = String;
+type Timestamp = i64;
+
+/// The message type of the topics to import.
+const DETECTIONS: &str = "vision_msgs/Detection2DArray";
+
+/// The ROS1 bag (version 2.0) format.
+///
+/// The input may either be a single bag or a directory of bags where each bag
+/// forms a stream. Each `vision_msgs/Detection2DArray` topic maps to a channel
+/// and the messages are grouped into frames by their header timestamps.
+pub struct Rosbag<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Rosbag<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", RosbagDebug::from(msg));
+        }
+    }
+
+    /// The set of bags to import.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "bag").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Load the set of detections of a bag, ordered by timestamp.
+    ///
+    /// The header timestamp of each message is used, if set; otherwise, the
+    /// time the message was recorded is used, instead.
+    fn load(
+        &self,
+        path: &Path,
+        options: &BagOptions,
+    ) -> Result<Vec<(Timestamp, Topic, RosDetections)>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(RosbagError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let mut reader = BagReader::new(BufReader::new(infile))
+            .map_err(|e| RosbagError::from(format!("{}: {}", path.display(), e)))?;
+
+        let keep = |x: &BagConnection| normalize(&x.kind) == DETECTIONS && options.keep(&x.topic);
+        let messages = reader
+            .messages(keep)
+            .map_err(|e| RosbagError::from(format!("{}: {}", path.display(), e)))?;
+
+        // Set up internal database.
+        //
+        // The set of message definitions is parsed once per connection.
+        let mut registries = HashMap::new();
+
+        for connection in reader.connections.values().filter(|x| keep(x)) {
+            let registry = RosRegistry::parse(&connection.kind, &connection.definition)
+                .map_err(|e| RosbagError::from(format!("{}: {}", connection.topic, e)))?;
+
+            self.debug(&format!(
+                "found topic `{}` ({})",
+                connection.topic, connection.kind
+            ));
+
+            registries.insert(connection.id, (connection.topic.clone(), registry));
+        }
+
+        let mut res = Vec::new();
+
+        for message in messages {
+            let (topic, registry) = &registries[&message.connection];
+
+            let detections = RosDecoder::new(registry, &message.data)
+                .decode()
+                .and_then(|x| RosDetections::from_value(&x))
+                .map_err(|e| RosbagError::from(format!("{}: {}", topic, e)))?;
+
+            res.push((
+                detections.stamp.unwrap_or(message.time),
+                topic.clone(),
+                detections,
+            ));
+        }
+
+        res.sort_by_key(|(stamp, _, _)| *stamp);
+
+        self.debug(&format!(
+            "decoded {} messages from `{}`",
+            res.len(),
+            path.display()
+        ));
+
+        Ok(res)
+    }
+}
+
+impl Schema for Rosbag<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: BagOptions = self.config.options("rosbag")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let detections = self.load(&path, &options)?;
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frame::frames(detections, &options)));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RosbagDebug {
+    msg: String,
+}
+
+impl From<&str> for RosbagDebug {
+    fn from(msg: &str) -> Self {
+        RosbagDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for RosbagDebug {
+    fn from(msg: String) -> Self {
+        RosbagDebug { msg }
+    }
+}
+
+impl fmt::Display for RosbagDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: rosbag: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RosbagError {
+    msg: String,
+}
+
+impl From<&str> for RosbagError {
+    fn from(msg: &str) -> Self {
+        RosbagError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for RosbagError {
+    fn from(msg: String) -> Self {
+        RosbagError { msg }
+    }
+}
+
+impl fmt::Display for RosbagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rosbag: {}", self.msg)
+    }
+}
+
+impl Error for RosbagError {}
@@ -0,0 +1,467 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+/// The magic line of the version 2.0 format.
+const MAGIC: &[u8] = b"#ROSBAG V2.0\n";
+
+/// The set of record types (i.e., `op` codes).
+const OP_MESSAGE: u8 = 0x02;
+const OP_BAG_HEADER: u8 = 0x03;
+const OP_CHUNK: u8 = 0x05;
+const OP_CONNECTION: u8 = 0x07;
+
+/// A connection (i.e., a topic and its message type).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Connection {
+    pub id: u32,
+    pub topic: String,
+    /// The message type (e.g., `vision_msgs/Detection2DArray`).
+    pub kind: String,
+    /// The full message definition, including its dependencies.
+    pub definition: String,
+}
+
+/// A serialized message of a connection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub connection: u32,
+    /// The time the message was recorded (in nanoseconds).
+    pub time: i64,
+    pub data: Vec<u8>,
+}
+
+/// A single record of the bag.
+///
+/// The header is a set of `name=value` fields where each field is prefixed by
+/// its length as a `u32`.
+struct Record {
+    header: HashMap<String, Vec<u8>>,
+    data: Vec<u8>,
+}
+
+impl Record {
+    /// Read the next record, if any.
+    fn read<R: Read>(reader: &mut R) -> Result<Option<Self>, Box<dyn Error>> {
+        let mut length = [0u8; 4];
+
+        match reader.read_exact(&mut length) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(Box::new(e)),
+        }
+
+        let header = fields(&bytes(reader, u32::from_le_bytes(length))?)?;
+
+        reader.read_exact(&mut length)?;
+
+        let data = bytes(reader, u32::from_le_bytes(length))?;
+
+        Ok(Some(Record { header, data }))
+    }
+
+    fn field(&self, name: &str) -> Result<&[u8], Box<dyn Error>> {
+        Ok(self
+            .header
+            .get(name)
+            .ok_or_else(|| format!("missing record field `{}`", name))?)
+    }
+
+    fn op(&self) -> Result<u8, Box<dyn Error>> {
+        Ok(*self.field("op")?.first().ok_or("empty record field `op`")?)
+    }
+
+    fn u32(&self, name: &str) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.field(name)?.try_into()?))
+    }
+
+    fn u64(&self, name: &str) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.field(name)?.try_into()?))
+    }
+
+    fn string(&self, name: &str) -> Result<String, Box<dyn Error>> {
+        Ok(String::from_utf8_lossy(self.field(name)?).into_owned())
+    }
+}
+
+/// Read a length-prefixed set of bytes.
+///
+/// The length is not trusted and, as such, not allocated up front such that a
+/// corrupt length results in an error rather than an excessive allocation.
+fn bytes<R: Read>(reader: &mut R, length: u32) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut buf = Vec::new();
+    reader.take(length as u64).read_to_end(&mut buf)?;
+
+    if buf.len() != length as usize {
+        return Err("truncated record".into());
+    }
+
+    Ok(buf)
+}
+
+/// Parse a set of length-prefixed `name=value` fields.
+fn fields(mut buf: &[u8]) -> Result<HashMap<String, Vec<u8>>, Box<dyn Error>> {
+    let mut res = HashMap::new();
+
+    while buf.len() >= 4 {
+        let length = u32::from_le_bytes(buf[..4].try_into()?) as usize;
+        let field = buf.get(4..4 + length).ok_or("truncated record header")?;
+
+        let split = field
+            .iter()
+            .position(|x| *x == b'=')
+            .ok_or("invalid record header field")?;
+
+        res.insert(
+            String::from_utf8_lossy(&field[..split]).into_owned(),
+            field[split + 1..].to_vec(),
+        );
+
+        buf = &buf[4 + length..];
+    }
+
+    Ok(res)
+}
+
+/// A reader of ROS1 bag (version 2.0) files.
+///
+/// The set of connections is first read from the index (at the end of the
+/// file) such that each message may be filtered by its connection as the
+/// chunks are read.
+pub struct BagReader<R: Read + Seek> {
+    reader: R,
+    pub connections: BTreeMap<u32, Connection>,
+}
+
+impl<R: Read + Seek> BagReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut magic = [0u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+
+        if magic != MAGIC {
+            return Err("not a bag (version 2.0) file".into());
+        }
+
+        let header = Record::read(&mut reader)?.ok_or("missing bag header")?;
+
+        if header.op()? != OP_BAG_HEADER {
+            return Err("missing bag header".into());
+        }
+
+        let start = reader.stream_position()?;
+        let index = header.u64("index_pos")?;
+
+        let mut bag = BagReader {
+            reader,
+            connections: BTreeMap::new(),
+        };
+
+        // An unindexed bag (i.e., one that was not closed properly) does not
+        // provide the set of connections; therefore, the connections are
+        // only collected as they are found within the chunks.
+        if index > 0 {
+            bag.reader.seek(SeekFrom::Start(index))?;
+
+            while let Some(record) = Record::read(&mut bag.reader)? {
+                if record.op()? == OP_CONNECTION {
+                    bag.connection(&record)?;
+                }
+            }
+
+            bag.reader.seek(SeekFrom::Start(start))?;
+        }
+
+        Ok(bag)
+    }
+
+    fn connection(&mut self, record: &Record) -> Result<(), Box<dyn Error>> {
+        let id = record.u32("conn")?;
+
+        if self.connections.contains_key(&id) {
+            return Ok(());
+        }
+
+        let data = fields(&record.data)?;
+        let field = |name: &str| {
+            data.get(name)
+                .map(|x| String::from_utf8_lossy(x).into_owned())
+                .unwrap_or_default()
+        };
+
+        self.connections.insert(
+            id,
+            Connection {
+                id,
+                topic: record.string("topic")?,
+                kind: field("type"),
+                definition: field("message_definition"),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Decompress the data of a chunk.
+    ///
+    /// The output is limited to the (uncompressed) `size` of the chunk header
+    /// such that a corrupt chunk can not inflate without bound.
+    fn decompress(&self, record: &Record) -> Result<Vec<u8>, Box<dyn Error>> {
+        let compression = record.string("compression")?;
+        let size = record.u32("size")? as u64;
+        let mut data = Vec::new();
+
+        match compression.as_str() {
+            "none" => return Ok(record.data.clone()),
+            "bz2" => {
+                bzip2::read::BzDecoder::new(&record.data[..])
+                    .take(size)
+                    .read_to_end(&mut data)?;
+            }
+            "lz4" => {
+                lz4_flex::frame::FrameDecoder::new(&record.data[..])
+                    .take(size)
+                    .read_to_end(&mut data)?;
+            }
+            x => return Err(format!("unsupported chunk compression `{}`", x).into()),
+        }
+
+        if data.len() as u64 != size {
+            return Err("chunk size mismatch".into());
+        }
+
+        Ok(data)
+    }
+
+    fn message(&self, record: Record) -> Result<Message, Box<dyn Error>> {
+        let time = record.field("time")?;
+
+        if time.len() != 8 {
+            return Err(format!("expected 8 bytes for `time`, found {}", time.len()).into());
+        }
+
+        let sec = u32::from_le_bytes(time[..4].try_into()?) as i64;
+        let nsec = u32::from_le_bytes(time[4..].try_into()?) as i64;
+
+        Ok(Message {
+            connection: record.u32("conn")?,
+            time: sec * 1_000_000_000 + nsec,
+            data: record.data,
+        })
+    }
+
+    /// Read the set of messages of the connections that pass the filter.
+    pub fn messages<F>(&mut self, filter: F) -> Result<Vec<Message>, Box<dyn Error>>
+    where
+        F: Fn(&Connection) -> bool,
+    {
+        let mut res = Vec::new();
+
+        while let Some(record) = Record::read(&mut self.reader)? {
+            let records = match record.op()? {
+                OP_CHUNK => {
+                    let mut records = Vec::new();
+                    let data = self.decompress(&record)?;
+                    let mut reader = &data[..];
+
+                    while let Some(record) = Record::read(&mut reader)? {
+                        records.push(record);
+                    }
+
+                    records
+                }
+                _ => vec![record],
+            };
+
+            for record in records {
+                match record.op()? {
+                    OP_CONNECTION => self.connection(&record)?,
+                    OP_MESSAGE => {
+                        let id = record.u32("conn")?;
+                        let connection = self
+                            .connections
+                            .get(&id)
+                            .ok_or_else(|| format!("message of unknown connection `{}`", id))?;
+
+                        if filter(connection) {
+                            res.push(self.message(record)?);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn header(fields: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        for (name, value) in fields {
+            buf.extend(((name.len() + 1 + value.len()) as u32).to_le_bytes());
+            buf.extend(name.as_bytes());
+            buf.push(b'=');
+            buf.extend(*value);
+        }
+
+        buf
+    }
+
+    fn record(fields: &[(&str, &[u8])], data: &[u8]) -> Vec<u8> {
+        let header = header(fields);
+
+        let mut buf = (header.len() as u32).to_le_bytes().to_vec();
+        buf.extend(header);
+        buf.extend((data.len() as u32).to_le_bytes());
+        buf.extend(data);
+
+        buf
+    }
+
+    fn connection() -> Vec<u8> {
+        record(
+            &[
+                ("op", &[OP_CONNECTION]),
+                ("conn", &0u32.to_le_bytes()),
+                ("topic", b"/detections"),
+            ],
+            &header(&[
+                ("type", b"vision_msgs/Detection2DArray"),
+                ("message_definition", b"Header header"),
+            ]),
+        )
+    }
+
+    fn message(time: &[u8]) -> Vec<u8> {
+        record(
+            &[
+                ("op", &[OP_MESSAGE]),
+                ("conn", &0u32.to_le_bytes()),
+                ("time", time),
+            ],
+            &[1, 2, 3],
+        )
+    }
+
+    /// Construct an unindexed bag of a single chunk.
+    fn bag(time: &[u8]) -> Vec<u8> {
+        let chunk = [connection(), message(time)].concat();
+        chunked(b"none", chunk.len() as u32, &chunk)
+    }
+
+    /// Construct an unindexed bag of a single chunk of the given compression
+    /// and (uncompressed) size.
+    fn chunked(compression: &[u8], size: u32, data: &[u8]) -> Vec<u8> {
+        [
+            MAGIC.to_vec(),
+            record(
+                &[("op", &[OP_BAG_HEADER]), ("index_pos", &0u64.to_le_bytes())],
+                &[],
+            ),
+            record(
+                &[
+                    ("op", &[OP_CHUNK]),
+                    ("compression", compression),
+                    ("size", &size.to_le_bytes()),
+                ],
+                data,
+            ),
+        ]
+        .concat()
+    }
+
+    fn lz4(data: &[u8]) -> Vec<u8> {
+        let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        std::io::Write::write_all(&mut encoder, data).unwrap();
+
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn messages() {
+        let time = [2u32.to_le_bytes(), 5u32.to_le_bytes()].concat();
+        let mut reader = BagReader::new(Cursor::new(bag(&time))).unwrap();
+
+        let messages = reader.messages(|_| true).unwrap();
+
+        assert_eq!(
+            reader.connections.get(&0),
+            Some(&Connection {
+                id: 0,
+                topic: String::from("/detections"),
+                kind: String::from("vision_msgs/Detection2DArray"),
+                definition: String::from("Header header"),
+            })
+        );
+
+        assert_eq!(
+            messages,
+            vec![Message {
+                connection: 0,
+                time: 2_000_000_005,
+                data: vec![1, 2, 3],
+            }]
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let time = [0u8; 8];
+        let mut reader = BagReader::new(Cursor::new(bag(&time))).unwrap();
+
+        assert!(reader
+            .messages(|x| x.topic != "/detections")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn compressed() {
+        let chunk = [connection(), message(&[0u8; 8])].concat();
+        let buf = chunked(b"lz4", chunk.len() as u32, &lz4(&chunk));
+
+        let mut reader = BagReader::new(Cursor::new(buf)).unwrap();
+
+        assert_eq!(reader.messages(|_| true).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn compressed_size() {
+        // A chunk that inflates beyond its size is cut off at the size.
+        let chunk = [connection(), message(&[0u8; 8])].concat();
+        let buf = chunked(b"lz4", 16, &lz4(&chunk));
+
+        let mut reader = BagReader::new(Cursor::new(buf)).unwrap();
+
+        assert!(reader.messages(|_| true).is_err());
+    }
+
+    #[test]
+    fn malformed_time() {
+        let mut reader = BagReader::new(Cursor::new(bag(&[0, 1]))).unwrap();
+
+        assert!(reader.messages(|_| true).is_err());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut buf = bag(&[0u8; 8]);
+        buf.truncate(buf.len() - 2);
+
+        let mut reader = BagReader::new(Cursor::new(buf)).unwrap();
+
+        assert!(reader.messages(|_| true).is_err());
+    }
+
+    #[test]
+    fn magic() {
+        assert!(BagReader::new(Cursor::new(b"#ROSBAG V1.2\n".to_vec())).is_err());
+    }
+}
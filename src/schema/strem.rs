@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+use strem::datastream::io::importer::DataImporter;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+/// A normalize-and-reexport path for existing STREM files.
+///
+/// The files are imported with [`DataImporter`] and canonicalized such that
+/// re-exporting them produces a stable output. The input may either be a
+/// single STREM file or a directory of STREM files.
+pub struct Strem<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Strem<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    /// Load the set of [`Frame`] from a STREM formatted file.
+    fn load(&self, path: &Path) -> Result<Vec<Frame>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(StremError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        let reader = BufReader::new(infile);
+        let data: Value = serde_json::from_reader(reader)?;
+
+        self.debug(&format!(
+            "serde: deserialized data from `{}`",
+            path.display()
+        ));
+
+        DataImporter::new().import(&data)
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", StremDebug::from(msg));
+        }
+    }
+
+    fn warn(&self, msg: &str) {
+        eprintln!("{}", StremWarning::from(msg));
+    }
+
+    /// The set of STREM files to normalize.
+    ///
+    /// If a directory is provided, each JSON file within it is considered,
+    /// ordered by its file name. The stream-level metadata written alongside
+    /// each stream (i.e., `<name>.meta.json`) is skipped, accordingly.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            let name = path.file_name().unwrap().to_string_lossy();
+
+            if path.is_file() && name.ends_with(".json") && !name.ends_with(".meta.json") {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Canonicalize the set of [`Frame`].
+    ///
+    /// This (1) re-indexes the frames such that they are contiguous from zero
+    /// (in the order of their original index), (2) drops empty label entries,
+    /// and (3) drops records that contain no annotations. Each fix is
+    /// reported, accordingly.
+    fn normalize(&self, name: &str, mut frames: Vec<Frame>) -> Vec<Frame> {
+        let mut reindexed = 0;
+        let mut labels = 0;
+        let mut records = 0;
+
+        // The order of the frames within the file is not guaranteed to follow
+        // their index; therefore, the frames are sorted before re-indexing.
+        frames.sort_by_key(|x| x.index);
+
+        for (index, frame) in frames.iter_mut().enumerate() {
+            if frame.index != index {
+                frame.index = index;
+                reindexed += 1;
+            }
+
+            let before = frame.samples.len();
+
+            frame.samples.retain_mut(|sample| match sample {
+                Sample::ObjectDetection(record) => {
+                    let n = record.annotations.len();
+                    record.annotations.retain(|_, x| !x.is_empty());
+                    labels += n - record.annotations.len();
+
+                    !record.annotations.is_empty()
+                }
+            });
+
+            records += before - frame.samples.len();
+        }
+
+        if reindexed > 0 {
+            self.warn(&format!("{}: re-indexed {} frame(s)", name, reindexed));
+        }
+
+        if labels > 0 {
+            self.warn(&format!("{}: dropped {} empty label(s)", name, labels));
+        }
+
+        if records > 0 {
+            self.warn(&format!("{}: dropped {} empty record(s)", name, records));
+        }
+
+        frames
+    }
+}
+
+impl Schema for Strem<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let name = path
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("strem"));
+
+            let frames = self.load(&path)?;
+            let frames = self.normalize(&name, frames);
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StremDebug {
+    msg: String,
+}
+
+impl From<&str> for StremDebug {
+    fn from(msg: &str) -> Self {
+        StremDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for StremDebug {
+    fn from(msg: String) -> Self {
+        StremDebug { msg }
+    }
+}
+
+impl fmt::Display for StremDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: strem: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StremWarning {
+    msg: String,
+}
+
+impl From<&str> for StremWarning {
+    fn from(msg: &str) -> Self {
+        StremWarning {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for StremWarning {
+    fn from(msg: String) -> Self {
+        StremWarning { msg }
+    }
+}
+
+impl fmt::Display for StremWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "stremf: warning: strem: {}", self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StremError {
+    msg: String,
+}
+
+impl From<&str> for StremError {
+    fn from(msg: &str) -> Self {
+        StremError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for StremError {
+    fn from(msg: String) -> Self {
+        StremError { msg }
+    }
+}
+
+impl fmt::Display for StremError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "strem: {}", self.msg)
+    }
+}
+
+impl Error for StremError {}
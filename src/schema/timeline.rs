@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+/// A timestamp of a sensor (e.g., in microseconds or nanoseconds).
+type Timestamp = i64;
+
+/// Find the nearest entry to the timestamp.
+pub fn nearest<V>(map: &BTreeMap<Timestamp, V>, timestamp: Timestamp) -> Option<(Timestamp, &V)> {
+    let before = map.range(..=timestamp).next_back();
+    let after = map.range(timestamp..).next();
+
+    match (before, after) {
+        (Some(a), Some(b)) => {
+            if timestamp - a.0 <= b.0 - timestamp {
+                Some((*a.0, a.1))
+            } else {
+                Some((*b.0, b.1))
+            }
+        }
+        (Some(x), None) | (None, Some(x)) => Some((*x.0, x.1)),
+        (None, None) => None,
+    }
+}
+
+/// Find the nearest entry to the timestamp, within the tolerance.
+///
+/// An entry further away (e.g., the neighbor of a dropped frame) is never
+/// associated with the timestamp.
+pub fn within<V>(
+    map: &BTreeMap<Timestamp, V>,
+    timestamp: Timestamp,
+    tolerance: Timestamp,
+) -> Option<(Timestamp, &V)> {
+    nearest(map, timestamp).filter(|x| (x.0 - timestamp).abs() <= tolerance)
+}
+
+/// The tolerance of a sensor (i.e., half of its period).
+///
+/// The period is estimated as the median difference between the entries such
+/// that a dropped frame does not affect it. If fewer than two entries exist,
+/// then the nominal period is used, instead.
+pub fn tolerance<V>(map: &BTreeMap<Timestamp, V>, nominal: Timestamp) -> Timestamp {
+    let timestamps = map.keys().collect::<Vec<&Timestamp>>();
+
+    let mut deltas = timestamps
+        .windows(2)
+        .map(|x| x[1] - x[0])
+        .collect::<Vec<Timestamp>>();
+
+    deltas.sort();
+    deltas.get(deltas.len() / 2).copied().unwrap_or(nominal) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest() {
+        let map = BTreeMap::from([(10, 'a'), (20, 'b'), (40, 'c')]);
+
+        assert_eq!(super::nearest(&map, 14), Some((10, &'a')));
+        assert_eq!(super::nearest(&map, 15), Some((10, &'a')));
+        assert_eq!(super::nearest(&map, 16), Some((20, &'b')));
+        assert_eq!(super::nearest(&map, 100), Some((40, &'c')));
+        assert_eq!(super::nearest(&BTreeMap::<i64, char>::new(), 0), None);
+    }
+
+    #[test]
+    fn within() {
+        // The frame at `40` is dropped.
+        let map = BTreeMap::from([(10, 'a'), (20, 'b'), (30, 'c'), (50, 'd')]);
+        let tolerance = tolerance(&map, 100);
+
+        assert_eq!(tolerance, 5);
+        assert_eq!(super::within(&map, 34, tolerance), Some((30, &'c')));
+        assert_eq!(super::within(&map, 40, tolerance), None);
+        assert_eq!(super::within(&map, 56, tolerance), None);
+    }
+
+    #[test]
+    fn nominal() {
+        assert_eq!(tolerance(&BTreeMap::from([(10, 'a')]), 100), 50);
+    }
+}
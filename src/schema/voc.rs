@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::annotation::{Annotation as VocAnnotation, Object as VocObject};
+use self::options::Options as VocOptions;
+
+mod annotation;
+mod options;
+
+/// The Pascal VOC detection dataset (e.g., `VOC2007`, `VOC2012`).
+///
+/// The root is the year directory containing `Annotations`, `ImageSets`, and
+/// `JPEGImages`. Each list of `ImageSets/Main` forms a stream.
+pub struct Voc<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Voc<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", VocDebug::from(msg));
+        }
+    }
+
+    /// Load the set of lists from the `ImageSets/Main` directory.
+    ///
+    /// Each list is named after its file (e.g., `train`, `val`). The per-class
+    /// lists (e.g., `car_train`) carry a second column marking the presence of
+    /// the class and are only imported when requested, in which case the
+    /// images without the class are skipped. If no `ImageSets/Main` directory
+    /// is found, then every annotation forms a single list.
+    fn sets(&self, options: &VocOptions) -> Result<Vec<(String, Vec<String>)>, Box<dyn Error>> {
+        let dir = self.root.join("ImageSets").join("Main");
+        let mut sets = Vec::new();
+
+        if dir.is_dir() {
+            let mut paths = fs::read_dir(&dir)?
+                .map(|x| x.map(|x| x.path()))
+                .collect::<Result<Vec<PathBuf>, _>>()?;
+
+            paths.sort();
+
+            for path in paths {
+                if path.extension().map(|x| x != "txt").unwrap_or(true) {
+                    continue;
+                }
+
+                let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+
+                let requested = match &options.sets {
+                    Some(sets) if !sets.contains(&name) => continue,
+                    Some(_) => true,
+                    None => false,
+                };
+
+                let mut ids = Vec::new();
+                let mut flagged = false;
+
+                for line in fs::read_to_string(&path)?.lines() {
+                    let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+                    match fields[..] {
+                        [id] => ids.push(id.to_string()),
+                        [id, flag] => {
+                            flagged = true;
+
+                            if flag != "-1" {
+                                ids.push(id.to_string());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                if flagged && !requested {
+                    continue;
+                }
+
+                self.debug(&format!("read list `{}` from `{}`", name, path.display()));
+                sets.push((name, ids));
+            }
+        } else {
+            let mut ids = Vec::new();
+
+            for entry in fs::read_dir(self.root.join("Annotations"))? {
+                let path = entry?.path();
+
+                if path.extension().map(|x| x == "xml").unwrap_or(false) {
+                    ids.push(path.file_stem().unwrap().to_string_lossy().into_owned());
+                }
+            }
+
+            ids.sort();
+
+            let name = self
+                .root
+                .file_name()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("voc"));
+
+            sets.push((name, ids));
+        }
+
+        Ok(sets)
+    }
+
+    /// Load the [`VocAnnotation`] of a single image.
+    fn load(&self, path: &Path) -> Result<VocAnnotation, Box<dyn Error>> {
+        let data = fs::read_to_string(path).or(Err(Box::new(VocError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        Ok(data
+            .parse()
+            .map_err(|e| VocError::from(format!("{}: {}", path.display(), e)))?)
+    }
+
+    /// Reference the image of the annotation.
+    ///
+    /// The dimensions are taken from the `<size>` of the annotation and, if
+    /// absent, read from the image itself.
+    fn image(&self, annotation: &VocAnnotation) -> Option<Image> {
+        let path = self.root.join("JPEGImages").join(&annotation.filename);
+
+        let (width, height) = match annotation.size {
+            Some(size) => size,
+            None => {
+                let size = imagesize::size(&path).ok()?;
+                (size.width as u32, size.height as u32)
+            }
+        };
+
+        Some(Image::new(ImageSource::File(path), width, height))
+    }
+
+    fn annotations(
+        &self,
+        objects: &[VocObject],
+        options: &VocOptions,
+    ) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for object in objects.iter().filter(|x| options.keep(x)) {
+            let [xmin, ymin, xmax, ymax] = object.bbox;
+
+            let width = xmax - xmin;
+            let height = ymax - ymin;
+
+            res.entry(object.name.clone())
+                .or_default()
+                .push(Annotation::new(
+                    object.name.clone(),
+                    1.0,
+                    BoundingBox::AxisAligned(aa::Region::new(
+                        Point::new(xmin + (width / 2.0), ymin + (height / 2.0)),
+                        width,
+                        height,
+                    )),
+                ));
+        }
+
+        res
+    }
+}
+
+impl Schema for Voc<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let options: VocOptions = self.config.options("voc")?;
+
+        // Construct the set of [`Frame`].
+        //
+        // This will loop through each list and collect the annotation of each
+        // listed image into a linear stream, in the order of the list.
+        let mut datastreams = Vec::new();
+
+        for (name, ids) in self.sets(&options)? {
+            let mut frames = Vec::new();
+
+            for (index, id) in ids.iter().enumerate() {
+                let annotation =
+                    self.load(&self.root.join("Annotations").join(format!("{}.xml", id)))?;
+
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(String::from("cam"), self.image(&annotation));
+
+                record.annotations = self.annotations(&annotation.objects, &options);
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VocDebug {
+    msg: String,
+}
+
+impl From<&str> for VocDebug {
+    fn from(msg: &str) -> Self {
+        VocDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for VocDebug {
+    fn from(msg: String) -> Self {
+        VocDebug { msg }
+    }
+}
+
+impl fmt::Display for VocDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: voc: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VocError {
+    msg: String,
+}
+
+impl From<&str> for VocError {
+    fn from(msg: &str) -> Self {
+        VocError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for VocError {
+    fn from(msg: String) -> Self {
+        VocError { msg }
+    }
+}
+
+impl fmt::Display for VocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "voc: {}", self.msg)
+    }
+}
+
+impl Error for VocError {}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use roxmltree::{Document, Node};
+
+/// A single `<object>` of a Pascal VOC annotation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+    pub name: String,
+    pub truncated: bool,
+    pub difficult: bool,
+    /// xmin, ymin, xmax, ymax (in pixels)
+    pub bbox: [f64; 4],
+}
+
+/// A Pascal VOC annotation file (i.e., one image).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub filename: String,
+    /// width, height (in pixels)
+    pub size: Option<(u32, u32)>,
+    pub objects: Vec<Object>,
+}
+
+/// Find the first direct child element by name.
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|x| x.is_element() && x.has_tag_name(name))
+}
+
+/// Find the (trimmed) text of the first direct child element by name.
+fn text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|x| x.text()).map(|x| x.trim())
+}
+
+/// Interpret a `0`/`1` flag, where a missing flag is not set.
+fn flag(node: Node, name: &str) -> bool {
+    matches!(text(node, name), Some("1"))
+}
+
+impl Object {
+    fn from_node(node: Node) -> Result<Self, Box<dyn Error>> {
+        let name = text(node, "name").ok_or("missing object `name`")?;
+        let bndbox = child(node, "bndbox").ok_or("missing object `bndbox`")?;
+
+        let mut bbox = [0.0; 4];
+
+        for (i, key) in ["xmin", "ymin", "xmax", "ymax"].iter().enumerate() {
+            bbox[i] = text(bndbox, key)
+                .ok_or_else(|| format!("missing bndbox `{}`", key))?
+                .parse()?;
+        }
+
+        Ok(Object {
+            name: name.to_string(),
+            truncated: flag(node, "truncated"),
+            difficult: flag(node, "difficult"),
+            bbox,
+        })
+    }
+}
+
+impl FromStr for Annotation {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let document = Document::parse(s)?;
+        let root = document.root_element();
+
+        // The `<size>` may be absent or zero in some annotations.
+        //
+        // In which case, the dimensions must be read from the image itself.
+        let size = match child(root, "size") {
+            Some(size) => {
+                let width = text(size, "width").unwrap_or("0").parse::<u32>()?;
+                let height = text(size, "height").unwrap_or("0").parse::<u32>()?;
+
+                if width > 0 && height > 0 {
+                    Some((width, height))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        let objects = root
+            .children()
+            .filter(|x| x.is_element() && x.has_tag_name("object"))
+            .map(Object::from_node)
+            .collect::<Result<Vec<Object>, _>>()?;
+
+        Ok(Annotation {
+            filename: text(root, "filename")
+                .ok_or("missing `filename`")?
+                .to_string(),
+            size,
+            objects,
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+
+use super::annotation::Object;
+
+/// The set of options applied to Pascal VOC annotations.
+///
+/// By default, every list of `ImageSets/Main` (except the per-class lists)
+/// and every object is kept.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Options {
+    /// Keep objects marked as `difficult`.
+    pub difficult: bool,
+    /// Keep objects marked as `truncated`.
+    pub truncated: bool,
+    /// The set of lists (e.g., `train`, `val`) to import.
+    pub sets: Option<Vec<String>>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            difficult: true,
+            truncated: true,
+            sets: None,
+        }
+    }
+}
+
+impl Options {
+    /// Check if the [`Object`] passes the set of filters.
+    pub fn keep(&self, object: &Object) -> bool {
+        if object.difficult && !self.difficult {
+            return false;
+        }
+
+        if object.truncated && !self.truncated {
+            return false;
+        }
+
+        true
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::frame::{Calibration as WaymoCalibration, Frame as WaymoFrame, Label as WaymoLabel};
+use self::record::RecordReader;
+
+mod frame;
+mod proto;
+mod record;
+
+type CameraName = u64;
+
+/// The set of options of the Waymo Open Dataset.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+struct WaymoOptions {
+    /// The directory to extract the (embedded) camera images to.
+    ///
+    /// If not provided, the images are not extracted and the records do not
+    /// reference an image.
+    images: Option<PathBuf>,
+}
+
+/// The Waymo Open Dataset (Perception) camera labels.
+///
+/// The input may either be a single `.tfrecord` segment or a directory of
+/// segments where each segment forms its own stream.
+pub struct Waymo<'a> {
+    pub path: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Waymo<'a> {
+    pub fn new<P: Into<PathBuf>>(path: P, config: &'a Configuration) -> Self {
+        let path = path.into();
+        Self { path, config }
+    }
+
+    /// Open a segment for reading.
+    fn open(&self, path: &Path) -> Result<RecordReader<BufReader<File>>, Box<dyn Error>> {
+        let infile = File::open(path).or(Err(Box::new(WaymoError::from(format!(
+            "unable to open `{}`",
+            path.display()
+        )))))?;
+
+        Ok(RecordReader::new(BufReader::new(infile)))
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", WaymoDebug::from(msg));
+        }
+    }
+
+    /// Map the `CameraName.Name` enumeration to a channel.
+    fn channel(&self, name: CameraName) -> Option<String> {
+        match name {
+            1 => Some(String::from("cam::front")),
+            2 => Some(String::from("cam::front::left")),
+            3 => Some(String::from("cam::front::right")),
+            4 => Some(String::from("cam::side::left")),
+            5 => Some(String::from("cam::side::right")),
+            _ => None,
+        }
+    }
+
+    /// Map the `Label.Type` enumeration to a label.
+    fn label(&self, kind: u64) -> String {
+        String::from(match kind {
+            1 => "vehicle",
+            2 => "pedestrian",
+            3 => "sign",
+            4 => "cyclist",
+            _ => "unknown",
+        })
+    }
+
+    /// The set of segment files.
+    fn files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        if !self.path.is_dir() {
+            return Ok(vec![self.path.clone()]);
+        }
+
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(&self.path)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map(|x| x == "tfrecord").unwrap_or(false) {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Extract the camera image (if requested) and reference it.
+    fn image(
+        &self,
+        frame: &WaymoFrame,
+        camera: CameraName,
+        calibration: &WaymoCalibration,
+        dir: Option<&PathBuf>,
+    ) -> Result<Option<Image>, Box<dyn Error>> {
+        let dir = match dir {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+
+        let image = match frame.images.iter().find(|x| x.name == camera) {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+
+        let path = dir.join(format!("{}_{}.jpg", frame.timestamp, camera));
+        fs::write(&path, &image.image)?;
+
+        Ok(Some(Image::new(
+            ImageSource::File(path),
+            calibration.width,
+            calibration.height,
+        )))
+    }
+
+    fn annotations(&self, labels: &[WaymoLabel]) -> HashMap<String, Vec<Annotation>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        for label in labels {
+            let name = self.label(label.kind);
+
+            res.entry(name.clone()).or_default().push(Annotation::new(
+                name,
+                1.0,
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(label.center_x, label.center_y),
+                    label.length,
+                    label.width,
+                )),
+            ));
+        }
+
+        res
+    }
+}
+
+impl Schema for Waymo<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("input path at `{}`", self.path.display()));
+
+        let options: WaymoOptions = self.config.options("waymo")?;
+
+        let mut datastreams = Vec::new();
+
+        for path in self.files()? {
+            let mut name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let mut cameras: BTreeMap<CameraName, WaymoCalibration> = BTreeMap::new();
+            let mut dir = None;
+
+            // Construct the set of [`Frame`].
+            //
+            // Each record of the segment forms a [`Frame`] with one record per
+            // camera. The records are decoded one at a time as each embeds the
+            // full set of camera images.
+            let mut frames = Vec::new();
+
+            for (index, record) in self.open(&path)?.enumerate() {
+                let data = record.and_then(|x| WaymoFrame::decode(&x)).map_err(|e| {
+                    WaymoError::from(format!("{}: record {}: {}", path.display(), index, e))
+                })?;
+
+                // Set up the segment from the first record.
+                //
+                // The name and calibrations are provided as part of the context
+                // and are constant across the segment.
+                if index == 0 {
+                    if !data.context.is_empty() {
+                        name = data.context.clone();
+                    }
+
+                    if let Some(images) = &options.images {
+                        let path = images.join(&name);
+                        fs::create_dir_all(&path)?;
+
+                        dir = Some(path);
+                    }
+                }
+
+                for calibration in data.calibrations.iter() {
+                    cameras.entry(calibration.name).or_insert(WaymoCalibration {
+                        name: calibration.name,
+                        width: calibration.width,
+                        height: calibration.height,
+                    });
+                }
+
+                let mut frame = Frame::new(index);
+
+                for (camera, calibration) in cameras.iter() {
+                    if let Some(channel) = self.channel(*camera) {
+                        let image = self.image(&data, *camera, calibration, dir.as_ref())?;
+                        let mut record = DetectionRecord::new(channel, image);
+
+                        if let Some(labels) = data.camera_labels.iter().find(|x| x.name == *camera)
+                        {
+                            record.annotations = self.annotations(&labels.labels);
+                        }
+
+                        // INSERT
+                        frame.samples.push(Sample::ObjectDetection(record));
+                    }
+                }
+
+                frames.push(frame);
+            }
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WaymoDebug {
+    msg: String,
+}
+
+impl From<&str> for WaymoDebug {
+    fn from(msg: &str) -> Self {
+        WaymoDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for WaymoDebug {
+    fn from(msg: String) -> Self {
+        WaymoDebug { msg }
+    }
+}
+
+impl fmt::Display for WaymoDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: waymo: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WaymoError {
+    msg: String,
+}
+
+impl From<&str> for WaymoError {
+    fn from(msg: &str) -> Self {
+        WaymoError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for WaymoError {
+    fn from(msg: String) -> Self {
+        WaymoError { msg }
+    }
+}
+
+impl fmt::Display for WaymoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "waymo: {}", self.msg)
+    }
+}
+
+impl Error for WaymoError {}
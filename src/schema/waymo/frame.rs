@@ -0,0 +1,259 @@
+use std::error::Error;
+
+use super::proto::MessageReader;
+
+/// The `CameraCalibration` message (only the image dimensions are kept).
+#[derive(Debug, Default, PartialEq)]
+pub struct Calibration {
+    pub name: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The `Label` message of a camera (only the 2D box is kept).
+///
+/// For camera labels, the `length` is the extent along the image x-axis and
+/// the `width` is the extent along the image y-axis.
+#[derive(Debug, Default, PartialEq)]
+pub struct Label {
+    pub kind: u64,
+    pub center_x: f64,
+    pub center_y: f64,
+    pub length: f64,
+    pub width: f64,
+}
+
+/// The `CameraLabels` message.
+#[derive(Debug, Default, PartialEq)]
+pub struct CameraLabels {
+    pub name: u64,
+    pub labels: Vec<Label>,
+}
+
+/// The `CameraImage` message (only the encoded image is kept).
+#[derive(Debug, Default, PartialEq)]
+pub struct CameraImage {
+    pub name: u64,
+    pub image: Vec<u8>,
+}
+
+/// The `Frame` message of the Waymo Open Dataset.
+///
+/// Only the fields required to import the camera labels are decoded. The
+/// field numbers follow `waymo_open_dataset/dataset.proto` and `label.proto`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Frame {
+    pub context: String,
+    pub timestamp: i64,
+    pub calibrations: Vec<Calibration>,
+    pub images: Vec<CameraImage>,
+    pub camera_labels: Vec<CameraLabels>,
+}
+
+impl Frame {
+    pub fn decode(buf: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut frame = Frame::default();
+
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => frame.decode_context(x.bytes())?,
+                (2, x) => frame.timestamp = x.u64() as i64,
+                (4, x) => frame.images.push(CameraImage::decode(x.bytes())?),
+                (8, x) => frame.camera_labels.push(CameraLabels::decode(x.bytes())?),
+                _ => {}
+            }
+        }
+
+        Ok(frame)
+    }
+
+    fn decode_context(&mut self, buf: &[u8]) -> Result<(), Box<dyn Error>> {
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => self.context = x.string(),
+                (2, x) => self.calibrations.push(Calibration::decode(x.bytes())?),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Calibration {
+    fn decode(buf: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut calibration = Calibration::default();
+
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => calibration.name = x.u64(),
+                (4, x) => calibration.width = x.u64() as u32,
+                (5, x) => calibration.height = x.u64() as u32,
+                _ => {}
+            }
+        }
+
+        Ok(calibration)
+    }
+}
+
+impl CameraImage {
+    fn decode(buf: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut image = CameraImage::default();
+
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => image.name = x.u64(),
+                (2, x) => image.image = x.bytes().to_vec(),
+                _ => {}
+            }
+        }
+
+        Ok(image)
+    }
+}
+
+impl CameraLabels {
+    fn decode(buf: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut labels = CameraLabels::default();
+
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => labels.name = x.u64(),
+                (2, x) => labels.labels.push(Label::decode(x.bytes())?),
+                _ => {}
+            }
+        }
+
+        Ok(labels)
+    }
+}
+
+impl Label {
+    fn decode(buf: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut label = Label::default();
+
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => label.decode_box(x.bytes())?,
+                (3, x) => label.kind = x.u64(),
+                _ => {}
+            }
+        }
+
+        Ok(label)
+    }
+
+    fn decode_box(&mut self, buf: &[u8]) -> Result<(), Box<dyn Error>> {
+        for field in MessageReader::new(buf) {
+            match field? {
+                (1, x) => self.center_x = x.f64(),
+                (2, x) => self.center_y = x.f64(),
+                (4, x) => self.width = x.f64(),
+                (5, x) => self.length = x.f64(),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut x: u64, buf: &mut Vec<u8>) {
+        while x >= 0x80 {
+            buf.push((x as u8) | 0x80);
+            x >>= 7;
+        }
+
+        buf.push(x as u8);
+    }
+
+    fn message(tag: u32, data: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        varint(((tag as u64) << 3) | 2, &mut buf);
+        varint(data.len() as u64, &mut buf);
+        buf.extend(data);
+
+        buf
+    }
+
+    fn number(tag: u32, x: u64) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        varint((tag as u64) << 3, &mut buf);
+        varint(x, &mut buf);
+
+        buf
+    }
+
+    fn double(tag: u32, x: f64) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        varint(((tag as u64) << 3) | 1, &mut buf);
+        buf.extend(x.to_le_bytes());
+
+        buf
+    }
+
+    #[test]
+    fn decode() {
+        let calibration = [number(1, 1), number(4, 1920), number(5, 1280)].concat();
+        let context = [message(1, b"segment"), message(2, &calibration)].concat();
+
+        let bbox = [
+            double(1, 100.0),
+            double(2, 50.0),
+            double(4, 20.0),
+            double(5, 40.0),
+        ]
+        .concat();
+
+        let label = [message(1, &bbox), number(3, 1)].concat();
+        let labels = [number(1, 1), message(2, &label)].concat();
+        let image = [number(1, 1), message(2, b"jpeg")].concat();
+
+        let buf = [
+            message(1, &context),
+            number(2, 1557855899201990),
+            message(4, &image),
+            message(8, &labels),
+            // An unknown field is skipped.
+            number(15, 7),
+        ]
+        .concat();
+
+        let frame = Frame::decode(&buf).unwrap();
+
+        assert_eq!(
+            frame,
+            Frame {
+                context: String::from("segment"),
+                timestamp: 1557855899201990,
+                calibrations: vec![Calibration {
+                    name: 1,
+                    width: 1920,
+                    height: 1280,
+                }],
+                images: vec![CameraImage {
+                    name: 1,
+                    image: b"jpeg".to_vec(),
+                }],
+                camera_labels: vec![CameraLabels {
+                    name: 1,
+                    labels: vec![Label {
+                        kind: 1,
+                        center_x: 100.0,
+                        center_y: 50.0,
+                        length: 40.0,
+                        width: 20.0,
+                    }],
+                }],
+            }
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::error::Error;
+
+/// A single field of a Protocol Buffers message.
+///
+/// Only the wire types used by the Waymo Open Dataset are supported (i.e.,
+/// the deprecated group wire types are rejected).
+#[derive(Debug, PartialEq)]
+pub enum Field<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl Field<'_> {
+    pub fn u64(&self) -> u64 {
+        match self {
+            Field::Varint(x) | Field::Fixed64(x) => *x,
+            Field::Fixed32(x) => *x as u64,
+            Field::Bytes(_) => 0,
+        }
+    }
+
+    pub fn f64(&self) -> f64 {
+        match self {
+            Field::Fixed64(x) => f64::from_bits(*x),
+            Field::Fixed32(x) => f32::from_bits(*x) as f64,
+            Field::Varint(x) => *x as f64,
+            Field::Bytes(_) => 0.0,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Field::Bytes(x) => x,
+            _ => &[],
+        }
+    }
+
+    pub fn string(&self) -> String {
+        String::from_utf8_lossy(self.bytes()).into_owned()
+    }
+}
+
+/// A reader of the (tag, field) pairs of a Protocol Buffers message.
+///
+/// This performs no schema validation; unknown fields are simply provided
+/// to (and skipped by) the caller.
+pub struct MessageReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MessageReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn varint(&mut self) -> Result<u64, Box<dyn Error>> {
+        let mut res = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = *self.buf.get(self.pos).ok_or("truncated varint")?;
+            self.pos += 1;
+
+            res |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(res);
+            }
+        }
+
+        Err("malformed varint".into())
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        let end = self.pos.checked_add(n).ok_or("malformed length")?;
+        let res = self.buf.get(self.pos..end).ok_or("truncated field")?;
+        self.pos = end;
+
+        Ok(res)
+    }
+
+    fn field(&mut self) -> Result<(u32, Field<'a>), Box<dyn Error>> {
+        let key = self.varint()?;
+        let tag = (key >> 3) as u32;
+
+        let field = match key & 0x7 {
+            0 => Field::Varint(self.varint()?),
+            1 => Field::Fixed64(u64::from_le_bytes(self.take(8)?.try_into()?)),
+            2 => {
+                let n = self.varint()? as usize;
+                Field::Bytes(self.take(n)?)
+            }
+            5 => Field::Fixed32(u32::from_le_bytes(self.take(4)?.try_into()?)),
+            x => return Err(format!("unsupported wire type `{}`", x).into()),
+        };
+
+        Ok((tag, field))
+    }
+}
+
+impl<'a> Iterator for MessageReader<'a> {
+    type Item = Result<(u32, Field<'a>), Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+
+        Some(self.field())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(buf: &[u8]) -> Result<Vec<(u32, Field<'_>)>, Box<dyn Error>> {
+        MessageReader::new(buf).collect()
+    }
+
+    #[test]
+    fn varint() {
+        // The value `300` is encoded as `[0xac, 0x02]`.
+        let res = fields(&[0x08, 0xac, 0x02]).unwrap();
+        assert_eq!(res, vec![(1, Field::Varint(300))]);
+    }
+
+    #[test]
+    fn varint_max() {
+        let mut buf = vec![0x08];
+        buf.extend([0xff; 9]);
+        buf.push(0x01);
+
+        let res = fields(&buf).unwrap();
+        assert_eq!(res, vec![(1, Field::Varint(u64::MAX))]);
+    }
+
+    #[test]
+    fn varint_truncated() {
+        assert!(fields(&[0x08, 0xac]).is_err());
+    }
+
+    #[test]
+    fn varint_malformed() {
+        let mut buf = vec![0x08];
+        buf.extend([0xff; 11]);
+
+        assert!(fields(&buf).is_err());
+    }
+
+    #[test]
+    fn fixed() {
+        let mut buf = vec![0x11];
+        buf.extend(1.5f64.to_le_bytes());
+        buf.push(0x1d);
+        buf.extend(2.5f32.to_le_bytes());
+
+        let res = fields(&buf).unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!((res[0].0, res[0].1.f64()), (2, 1.5));
+        assert_eq!((res[1].0, res[1].1.f64()), (3, 2.5));
+    }
+
+    #[test]
+    fn bytes() {
+        let res = fields(&[0x22, 0x03, b'a', b'b', b'c']).unwrap();
+
+        assert_eq!(res, vec![(4, Field::Bytes(b"abc"))]);
+        assert_eq!(res[0].1.string(), "abc");
+    }
+
+    #[test]
+    fn bytes_truncated() {
+        assert!(fields(&[0x22, 0x05, b'a']).is_err());
+    }
+
+    #[test]
+    fn group_unsupported() {
+        assert!(fields(&[0x0b]).is_err());
+    }
+}
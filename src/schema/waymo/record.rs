@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::io::{ErrorKind, Read};
+
+/// A reader of TFRecord files.
+///
+/// Each record is stored as: (1) the length of the data as a little-endian
+/// `u64`, (2) the masked CRC32C of the length, (3) the data, and (4) the
+/// masked CRC32C of the data. The checksums are skipped and not verified.
+pub struct RecordReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the next record, if any.
+    pub fn next_record(&mut self) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        let mut header = [0u8; 12];
+
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(Box::new(e)),
+        }
+
+        let length = u64::from_le_bytes(header[..8].try_into()?);
+
+        // The length is read in bounded chunks (rather than allocated up
+        // front) such that a corrupt length results in an error.
+        let mut data = Vec::new();
+        (&mut self.reader).take(length).read_to_end(&mut data)?;
+
+        if data.len() as u64 != length {
+            return Err("truncated record".into());
+        }
+
+        let mut footer = [0u8; 4];
+        self.reader.read_exact(&mut footer)?;
+
+        Ok(Some(data))
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = Result<Vec<u8>, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(data: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        buf.extend((data.len() as u64).to_le_bytes());
+        buf.extend([0; 4]);
+        buf.extend(data);
+        buf.extend([0; 4]);
+
+        buf
+    }
+
+    #[test]
+    fn records() {
+        let mut buf = record(b"abc");
+        buf.extend(record(b""));
+
+        let res = RecordReader::new(&buf[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(res, vec![b"abc".to_vec(), Vec::new()]);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(RecordReader::new(&[][..]).next().is_none());
+    }
+
+    #[test]
+    fn truncated() {
+        let buf = record(b"abc");
+
+        let mut reader = RecordReader::new(&buf[..buf.len() - 6]);
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn length_overflow() {
+        let mut buf = Vec::new();
+        buf.extend(u64::MAX.to_le_bytes());
+        buf.extend([0; 8]);
+
+        let mut reader = RecordReader::new(&buf[..]);
+        assert!(reader.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use strem::datastream::frame::sample::detections::bbox::region::{aa, Point};
+use strem::datastream::frame::sample::detections::bbox::BoundingBox;
+use strem::datastream::frame::sample::detections::{
+    Annotation, DetectionRecord, Image, ImageSource,
+};
+use strem::datastream::frame::sample::Sample;
+use strem::datastream::frame::Frame;
+
+use crate::config::Configuration;
+
+use super::Schema;
+
+use self::dataset::Dataset as YoloDataset;
+use self::label::Label as YoloLabel;
+
+mod dataset;
+mod label;
+
+type ClassId = usize;
+
+/// The set of image extensions recognized within the `images/` tree.
+const EXTENSIONS: [&str; 7] = ["bmp", "jpeg", "jpg", "png", "tif", "tiff", "webp"];
+
+pub struct Yolo<'a> {
+    pub root: PathBuf,
+    pub config: &'a Configuration,
+}
+
+impl<'a> Yolo<'a> {
+    pub fn new<P: Into<PathBuf>>(root: P, config: &'a Configuration) -> Self {
+        let root = root.into();
+        Self { root, config }
+    }
+
+    fn debug(&self, msg: &str) {
+        if self.config.debug {
+            println!("{}", YoloDebug::from(msg));
+        }
+    }
+
+    /// Load the set of class names.
+    ///
+    /// The Ultralytics `data.yaml` file takes precedence over the Darknet
+    /// `classes.txt` (or `obj.names`) file where each line names the class of
+    /// the same index.
+    fn classes(&self) -> Result<BTreeMap<ClassId, String>, Box<dyn Error>> {
+        for filename in ["data.yaml", "data.yml"] {
+            let path = self.root.join(filename);
+
+            if path.is_file() {
+                let dataset: YoloDataset =
+                    serde_yaml::from_reader(BufReader::new(File::open(&path)?))?;
+
+                self.debug(&format!(
+                    "serde: deserialized data from `{}`",
+                    path.display()
+                ));
+
+                return Ok(dataset.classes());
+            }
+        }
+
+        for filename in ["classes.txt", "obj.names"] {
+            let path = self.root.join(filename);
+
+            if path.is_file() {
+                let mut classes = BTreeMap::new();
+
+                for (i, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+                    let line = line?;
+
+                    if !line.trim().is_empty() {
+                        classes.insert(i, line.trim().to_string());
+                    }
+                }
+
+                self.debug(&format!("read class names from `{}`", path.display()));
+
+                return Ok(classes);
+            }
+        }
+
+        Err(Box::new(YoloError::from(format!(
+            "unable to find `data.yaml` or `classes.txt` in `{}`",
+            self.root.display()
+        ))))
+    }
+
+    /// Recursively collect the images under the directory.
+    ///
+    /// The images are grouped by the directory (relative to `base`) in which
+    /// they reside such that each split/subdirectory forms its own stream.
+    fn images(
+        &self,
+        base: &Path,
+        dir: &Path,
+        res: &mut BTreeMap<PathBuf, Vec<PathBuf>>,
+    ) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                self.images(base, &path, res)?;
+            } else if path
+                .extension()
+                .map(|x| EXTENSIONS.contains(&&x.to_string_lossy().to_lowercase()[..]))
+                .unwrap_or(false)
+            {
+                let group = dir.strip_prefix(base)?.to_path_buf();
+                res.entry(group).or_default().push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the label file associated with an image.
+    ///
+    /// Following the Ultralytics convention, the label file is found by
+    /// replacing the `images` directory with `labels` and the extension with
+    /// `.txt`. If no such file exists, the Darknet convention of the label
+    /// file residing next to the image is used instead.
+    fn label(&self, group: &Path, image: &Path) -> PathBuf {
+        let filename = image.with_extension("txt");
+        let filename = filename.file_name().unwrap();
+
+        let path = self.root.join("labels").join(group).join(filename);
+
+        if path.is_file() {
+            path
+        } else {
+            image.with_extension("txt")
+        }
+    }
+
+    /// Name the stream after the split/subdirectory.
+    ///
+    /// Nested directories are joined with a `-` so that the name remains a
+    /// valid file name when exported.
+    fn name(&self, group: &Path) -> String {
+        let name = group
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<String>>()
+            .join("-");
+
+        if name.is_empty() {
+            self.root
+                .file_name()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("yolo"))
+        } else {
+            name
+        }
+    }
+
+    fn annotations(
+        &self,
+        path: &Path,
+        width: f64,
+        height: f64,
+        classes: &BTreeMap<ClassId, String>,
+    ) -> Result<HashMap<String, Vec<Annotation>>, Box<dyn Error>> {
+        let mut res: HashMap<String, Vec<Annotation>> = HashMap::new();
+
+        // An image without a label file is a background image.
+        //
+        // Therefore, it is kept as part of the stream but does not contain any
+        // annotations, accordingly.
+        if !path.is_file() {
+            return Ok(res);
+        }
+
+        for (i, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let label: YoloLabel = line
+                .parse()
+                .map_err(|e| YoloError::from(format!("{}:{}: {}", path.display(), i + 1, e)))?;
+
+            let name = classes.get(&label.class).ok_or_else(|| {
+                YoloError::from(format!(
+                    "{}:{}: unknown class `{}`",
+                    path.display(),
+                    i + 1,
+                    label.class
+                ))
+            })?;
+
+            // Convert the normalized coordinates into pixel coordinates.
+            //
+            // YOLO stores the box from its center, already; therefore, only
+            // scaling by the image dimensions is required.
+            res.entry(name.clone()).or_default().push(Annotation::new(
+                name.clone(),
+                label.confidence.unwrap_or(1.0),
+                BoundingBox::AxisAligned(aa::Region::new(
+                    Point::new(label.cx * width, label.cy * height),
+                    label.width * width,
+                    label.height * height,
+                )),
+            ));
+        }
+
+        Ok(res)
+    }
+}
+
+impl Schema for Yolo<'_> {
+    fn import(&self) -> Result<Vec<(String, Vec<Frame>)>, Box<dyn Error>> {
+        self.debug(&format!("root directory at `{}`", self.root.display()));
+
+        let classes = self.classes()?;
+
+        // Collect the set of images.
+        //
+        // The images are grouped by their split/subdirectory and ordered by
+        // their file name to form a linear stream.
+        let base = self.root.join("images");
+
+        if !base.is_dir() {
+            return Err(Box::new(YoloError::from(format!(
+                "unable to find `{}`",
+                base.display()
+            ))));
+        }
+
+        let mut groups = BTreeMap::new();
+        self.images(&base, &base, &mut groups)?;
+
+        // Construct the set of [`Frame`].
+        //
+        // The dimensions of each image are read from its header as the label
+        // files only store normalized coordinates.
+        let mut datastreams = Vec::new();
+
+        for (group, mut images) in groups {
+            images.sort();
+
+            let mut frames = Vec::new();
+
+            for (index, image) in images.iter().enumerate() {
+                let size = imagesize::size(image).map_err(|e| {
+                    YoloError::from(format!("unable to read `{}`: {}", image.display(), e))
+                })?;
+
+                let mut frame = Frame::new(index);
+                let mut record = DetectionRecord::new(
+                    String::from("cam"),
+                    Some(Image::new(
+                        ImageSource::File(image.clone()),
+                        size.width as u32,
+                        size.height as u32,
+                    )),
+                );
+
+                record.annotations = self.annotations(
+                    &self.label(&group, image),
+                    size.width as f64,
+                    size.height as f64,
+                    &classes,
+                )?;
+
+                // INSERT
+                frame.samples.push(Sample::ObjectDetection(record));
+                frames.push(frame);
+            }
+
+            let name = self.name(&group);
+
+            self.debug(&format!("imported stream `{}`", name));
+            datastreams.push((name, frames));
+        }
+
+        Ok(datastreams)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct YoloDebug {
+    msg: String,
+}
+
+impl From<&str> for YoloDebug {
+    fn from(msg: &str) -> Self {
+        YoloDebug {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for YoloDebug {
+    fn from(msg: String) -> Self {
+        YoloDebug { msg }
+    }
+}
+
+impl fmt::Display for YoloDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        write!(f, "DEBUG({:020}s): stremf: yolo: {}", timestamp, self.msg)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct YoloError {
+    msg: String,
+}
+
+impl From<&str> for YoloError {
+    fn from(msg: &str) -> Self {
+        YoloError {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<String> for YoloError {
+    fn from(msg: String) -> Self {
+        YoloError { msg }
+    }
+}
+
+impl fmt::Display for YoloError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "yolo: {}", self.msg)
+    }
+}
+
+impl Error for YoloError {}
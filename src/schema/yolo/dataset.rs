@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// The class names of a `data.yaml` file.
+///
+/// Ultralytics accepts the names as either a list (indexed by position) or a
+/// mapping from the class index to the name.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Names {
+    List(Vec<String>),
+    Map(BTreeMap<usize, String>),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Dataset {
+    pub names: Names,
+}
+
+impl Dataset {
+    /// Flatten the class names into a mapping from class index to name.
+    pub fn classes(self) -> BTreeMap<usize, String> {
+        match self.names {
+            Names::List(names) => names.into_iter().enumerate().collect(),
+            Names::Map(names) => names,
+        }
+    }
+}
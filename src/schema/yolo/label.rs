@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::str::FromStr;
+
+/// A single line of a YOLO label file.
+///
+/// The coordinates are normalized with respect to the image dimensions and
+/// describe the box from its center.
+#[derive(Debug, PartialEq)]
+pub struct Label {
+    pub class: usize,
+    pub cx: f64,
+    pub cy: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Only present in prediction files.
+    pub confidence: Option<f64>,
+}
+
+impl FromStr for Label {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+
+        if fields.len() != 5 && fields.len() != 6 {
+            return Err(format!("expected 5 or 6 fields, found {}", fields.len()).into());
+        }
+
+        Ok(Label {
+            class: fields[0].parse()?,
+            cx: fields[1].parse()?,
+            cy: fields[2].parse()?,
+            width: fields[3].parse()?,
+            height: fields[4].parse()?,
+            confidence: fields.get(5).map(|x| x.parse()).transpose()?,
+        })
+    }
+}